@@ -0,0 +1,64 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct PieMaterial {
+    center: vec2<f32>,
+    radii: vec2<f32>,
+    angles: vec2<f32>,
+    gap: f32,
+    color: vec4<f32>,
+};
+
+@group(2) @binding(0)
+var<uniform> material: PieMaterial;
+
+var<private> PI: f32 = 3.14159265359;
+
+fn rotate(p: vec2<f32>, angle: f32) -> vec2<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec2<f32>(c * p.x - s * p.y, s * p.x + c * p.y);
+}
+
+// signed distance to an annular sector going from angles.x to angles.y,
+// negative inside
+fn sd_slice(p: vec2<f32>, radii: vec2<f32>, angles: vec2<f32>) -> f32 {
+    let r = length(p);
+    let d_ring = max(radii.x - r, r - radii.y);
+
+    let half_angle = (angles.y - angles.x) / 2.0;
+    if (half_angle >= PI - 0.0001) {
+        return d_ring;
+    }
+
+    // the slice is symmetric around the x axis after this rotation
+    let q = rotate(p, -(angles.x + half_angle));
+    let qa = vec2<f32>(q.x, abs(q.y));
+    let dir = vec2<f32>(cos(half_angle), sin(half_angle));
+
+    // positive when qa is past the edge of the slice
+    let side = dir.x * qa.y - dir.y * qa.x;
+
+    var d_angle = side;
+    if (side > 0.0 && dot(qa, dir) < 0.0) {
+        d_angle = length(qa);
+    }
+
+    return max(d_ring, d_angle);
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = in.world_position.xy - material.center;
+
+    let d = sd_slice(p, material.radii, material.angles) + material.gap / 2.0;
+
+    // antialiasing over one pixel, whatever the scale of the camera
+    let aa = max(fwidth(d), 0.0001);
+    let alpha = 1.0 - smoothstep(-aa, aa, d);
+
+    if (alpha <= 0.0) {
+        discard;
+    }
+
+    return vec4<f32>(material.color.rgb, material.color.a * alpha);
+}
@@ -0,0 +1,24 @@
+#import bevy_sprite::mesh2d_vertex_output::VertexOutput
+
+struct ShapeMaterial {
+    canvas_position: vec2<f32>,
+    inner_size: vec2<f32>,
+};
+
+@group(2) @binding(0)
+var<uniform> material: ShapeMaterial;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    // discard everything outside of the inner area of the canvas
+    let local = in.world_position.xy - material.canvas_position;
+    if (abs(local.x) > material.inner_size.x / 2.0 || abs(local.y) > material.inner_size.y / 2.0) {
+        discard;
+    }
+
+#ifdef VERTEX_COLORS
+    return in.color;
+#else
+    return vec4<f32>(1.0, 1.0, 1.0, 1.0);
+#endif
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+
+    // the font is needed for the text of the annotations
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    commands.insert_resource(TickLabelFont { maybe_font: Some(font) });
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // frame times of a game session, in milliseconds
+    let frame_times = (0..60)
+        .map(|t| {
+            let t = t as f32;
+            let gc_pause = if (31.0..34.0).contains(&t) { 18.0 } else { 0.0 };
+            Vec2::new(t, 14.0 + 2.0 * (t * 0.9).sin() + gc_pause)
+        })
+        .collect::<Vec<Vec2>>();
+
+    plot.stemopt(frame_times, vec![Opt::Name("frame time".to_string())]);
+
+    // budget for 60 fps
+    plot.annotateopt(
+        Annotation::HLine(16.6),
+        vec![
+            Opt::Label("60 fps".to_string()),
+            Opt::Color(Color::srgb(0.3, 0.8, 0.4)),
+        ],
+    );
+
+    // game events stay on the edge of the canvas when scrolled out of view
+    plot.annotateopt(
+        Annotation::VLine(12.0),
+        vec![
+            Opt::Label("boss spawned".to_string()),
+            Opt::Color(Color::srgb(0.9, 0.4, 0.3)),
+            Opt::Clamp(true),
+        ],
+    );
+
+    plot.annotateopt(
+        Annotation::XSpan(30.5, 34.5),
+        vec![
+            Opt::Label("GC pause".to_string()),
+            Opt::Color(Color::srgb(0.9, 0.7, 0.2)),
+        ],
+    );
+
+    plot.annotateopt(
+        Annotation::Arrow(Vec2::new(42.0, 30.0), Vec2::new(34.5, 32.0)),
+        vec![Opt::Label("spike".to_string()), Opt::Size(1.5)],
+    );
+
+    plot.text(Vec2::new(50.0, 5.0), "level 2");
+
+    plots.add(plot.clone());
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // a random walk for the price: (time, open, high, low, close, volume)
+    let mut close = 100.0_f32;
+    let candles = (0..60)
+        .map(|day| {
+            let open = close;
+            close = open + 3.0 * (day as f32 * 1.7).sin() + 1.5 * (day as f32 * 0.37).cos();
+            let high = open.max(close) + 1.0 + (day % 3) as f32;
+            let low = open.min(close) - 1.0 - (day % 4) as f32;
+            let volume = 1000.0 + 400.0 * (day as f32 * 0.9).sin().abs();
+            (day as f32, open, high, low, close, volume)
+        })
+        .collect::<Vec<(f32, f32, f32, f32, f32, f32)>>();
+
+    plot.candlestickopt(candles, vec![Opt::Volume(true)]);
+
+    plots.add(plot.clone());
+}
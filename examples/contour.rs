@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+
+    // the font is needed for the inline level labels
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    commands.insert_resource(TickLabelFont { maybe_font: Some(font) });
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.set_bounds(Vec2::new(-3.0, -3.0), Vec2::new(3.0, 3.0));
+
+    let levels = (-4..=4).map(|k| k as f32 * 0.2).collect::<Vec<f32>>();
+
+    // the function is resampled over the visible range every time the axes are moved or zoomed
+    plot.contouropt(
+        ContourField::Function(peaks),
+        levels,
+        vec![
+            Opt::Fill(true),
+            Opt::Labels(true),
+            Opt::Color(Color::srgb(0.1, 0.1, 0.1)),
+            Opt::NumPoints(80),
+        ],
+    );
+
+    plots.add(plot.clone());
+}
+
+pub fn peaks(x: f32, y: f32) -> f32 {
+    (-(x * x + y * y) / 2.0).exp() - 0.6 * (-((x - 1.5).powi(2) + (y - 1.0).powi(2))).exp()
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (1000., 800.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<PlotMap>,
+    mut layout: ResMut<PlotLayout>,
+    mut links: ResMut<PlotLinks>,
+) {
+    commands.spawn(Camera2d::default());
+
+    // three timelines sampled every 100 ms
+    let timeline = |f: fn(f32) -> f32| {
+        (0..100)
+            .map(|i| Vec2::new(i as f32 * 0.1, f(i as f32 * 0.1)))
+            .collect::<Vec<Vec2>>()
+    };
+
+    let mut cpu = Plot::default();
+    cpu.stem(timeline(|t| 50.0 + 40.0 * (t * 1.3).sin()));
+
+    let mut gpu = Plot::default();
+    gpu.stem(timeline(|t| 30.0 + 25.0 * (t * 0.7).cos()));
+
+    let mut network = Plot::default();
+    network.stem(timeline(|t| 5.0 + 4.0 * (t * 3.1).sin().abs()));
+
+    let ids = vec![plots.add(cpu), plots.add(gpu), plots.add(network)];
+
+    let mut figure = Figure::new(3, 1);
+    for (row, id) in ids.iter().enumerate() {
+        figure.add(*id, row, 0);
+    }
+    layout.add(figure);
+
+    // panning or zooming any of the timelines scrolls the other two
+    links.link(ids, LinkedAxes::X);
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, add_samples)
+        .run();
+}
+
+#[derive(Resource)]
+struct LivePlot(PlotId);
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, mut layout: ResMut<PlotLayout>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+
+    plot.stemopt(vec![Vec2::ZERO], vec![Opt::Name("frame time".to_string())]);
+
+    // the view scrolls with the last 10 seconds of data. Pan or zoom to pause it, and press Alt+A to resume
+    plot.autoscale = Autoscale::follow(10.0);
+
+    // press Alt+T over two spikes to pin targets and measure the time between them, and Alt+Delete to remove one
+    let plot_id = plots.add(plot.clone());
+    commands.insert_resource(LivePlot(plot_id));
+
+    // a HUD graph in the top right corner of the window, whatever its size
+    layout.anchor(AnchoredPlot::new(
+        plot_id,
+        WindowAnchor::TopRight,
+        LayoutLength::Percent(60.0),
+        LayoutLength::Px(300.0),
+    ));
+}
+
+// appends a sample every tenth of a second
+fn add_samples(
+    time: Res<Time>,
+    live_plot: Res<LivePlot>,
+    mut plots: ResMut<PlotMap>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+) {
+    let Some(plot) = plots.get_mut(&live_plot.0) else {
+        return;
+    };
+
+    let stems = &mut plot.data.stem_groups[0];
+    let last_time = stems.data.last().map(|p| p.x).unwrap_or(0.0);
+    let now = time.elapsed_secs();
+
+    if now - last_time < 0.1 {
+        return;
+    }
+
+    let spike = if (now * 0.7).sin() > 0.95 { 8.0 } else { 0.0 };
+    stems.data.push(Vec2::new(now, 16.0 + 2.0 * (now * 3.0).sin() + spike));
+
+    // the range follows the data on its own, but the new stem must be drawn even when the view is paused
+    respawn_all_event.send(RespawnAllEvent { plot_id: live_plot.0 });
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+
+    // the font is needed for the slice labels
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    commands.insert_resource(TickLabelFont { maybe_font: Some(font) });
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.tick_label_color = Color::WHITE;
+
+    // end-of-match summary: two charts side by side on the same canvas
+    plot.pieopt(
+        vec![("Kills", 12.0), ("Assists", 7.0), ("Deaths", 4.0)],
+        vec![Opt::Percentages(true), Opt::Explode(0, 0.1)],
+    );
+
+    plot.pieopt(
+        vec![("Melee", 30.0), ("Ranged", 55.0), ("Magic", 15.0)],
+        vec![Opt::InnerRadius(0.5), Opt::StartAngle(0.0)],
+    );
+
+    plots.add(plot.clone());
+}
@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (add_samples, swing_screens))
+        .run();
+}
+
+#[derive(Resource)]
+struct LivePlot(PlotId);
+
+#[derive(Component)]
+struct Screen;
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, mut images: ResMut<Assets<Image>>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(400.0, 250.0);
+    plot.stemopt(vec![Vec2::ZERO], vec![Opt::Name("reactor".to_string())]);
+    plot.autoscale = Autoscale::follow(10.0);
+
+    // the plot is drawn offscreen on render layer 1. The image can also be the base_color_texture of
+    // a StandardMaterial for in-world terminals (see examples/plot_texture_3d.rs)
+    let image = plot.render_to_texture(&mut images, 1);
+
+    let plot_id = plots.add(plot);
+    commands.insert_resource(LivePlot(plot_id));
+
+    // two displays showing the same live graph
+    for (x, angle) in [(-180.0, 0.15), (200.0, -0.25)] {
+        commands.spawn((
+            Sprite {
+                image: image.clone(),
+                custom_size: Some(Vec2::new(320.0, 200.0)),
+                ..default()
+            },
+            Transform::from_xyz(x, 0.0, 0.0).with_rotation(Quat::from_rotation_z(angle)),
+            Screen,
+        ));
+    }
+}
+
+// appends a sample every tenth of a second
+fn add_samples(
+    time: Res<Time>,
+    live_plot: Res<LivePlot>,
+    mut plots: ResMut<PlotMap>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+) {
+    let Some(plot) = plots.get_mut(&live_plot.0) else {
+        return;
+    };
+
+    let stems = &mut plot.data.stem_groups[0];
+    let last_time = stems.data.last().map(|p| p.x).unwrap_or(0.0);
+    let now = time.elapsed_secs();
+
+    if now - last_time < 0.1 {
+        return;
+    }
+
+    stems
+        .data
+        .push(Vec2::new(now, 10.0 + 3.0 * (now * 2.0).sin()));
+    respawn_all_event.send(RespawnAllEvent {
+        plot_id: live_plot.0,
+    });
+}
+
+fn swing_screens(time: Res<Time>, mut screens: Query<&mut Transform, With<Screen>>) {
+    for (i, mut transform) in screens.iter_mut().enumerate() {
+        let phase = i as f32 * 1.5;
+        transform.scale.x = 1.0 + 0.1 * (time.elapsed_secs() + phase).sin();
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+// cargo run --example plot_texture_3d --features pbr
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (add_samples, turn_terminal))
+        .run();
+}
+
+#[derive(Resource)]
+struct LivePlot(PlotId);
+
+#[derive(Component)]
+struct Terminal;
+
+fn setup(
+    mut commands: Commands,
+    mut plots: ResMut<PlotMap>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 1.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+
+    commands.spawn((
+        PointLight {
+            intensity: 2_000_000.0,
+            ..default()
+        },
+        Transform::from_xyz(3.0, 4.0, 5.0),
+    ));
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(640.0, 400.0);
+    plot.stemopt(vec![Vec2::ZERO], vec![Opt::Name("reactor".to_string())]);
+    plot.autoscale = Autoscale::follow(10.0);
+
+    // the plot is drawn on render layer 1 by its own camera, before the 3d camera (order -1). The screen
+    // of the terminal is opaque, so the image is cleared with a dark color instead of a transparent one
+    let image = plot.render_to_texture(&mut images, 1);
+    if let Some(texture) = plot.render_target.as_mut() {
+        texture.clear_color = Color::srgb(0.05, 0.05, 0.08);
+    }
+
+    let plot_id = plots.add(plot);
+    commands.insert_resource(LivePlot(plot_id));
+
+    // the image is sRGB like any color texture, and a rectangle has the top of the image at the top
+    let screen = materials.add(StandardMaterial {
+        base_color_texture: Some(image),
+        unlit: true,
+        ..default()
+    });
+    let frame = materials.add(Color::srgb(0.3, 0.3, 0.35));
+
+    commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::new(3.4, 2.2, 0.2))),
+            MeshMaterial3d(frame),
+            Transform::default(),
+            Terminal,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Mesh3d(meshes.add(Rectangle::new(3.2, 2.0))),
+                MeshMaterial3d(screen),
+                Transform::from_xyz(0.0, 0.0, 0.101),
+            ));
+        });
+}
+
+// appends a sample every tenth of a second
+fn add_samples(
+    time: Res<Time>,
+    live_plot: Res<LivePlot>,
+    mut plots: ResMut<PlotMap>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+) {
+    let Some(plot) = plots.get_mut(&live_plot.0) else {
+        return;
+    };
+
+    let stems = &mut plot.data.stem_groups[0];
+    let last_time = stems.data.last().map(|p| p.x).unwrap_or(0.0);
+    let now = time.elapsed_secs();
+
+    if now - last_time < 0.1 {
+        return;
+    }
+
+    stems
+        .data
+        .push(Vec2::new(now, 10.0 + 3.0 * (now * 2.0).sin()));
+    respawn_all_event.send(RespawnAllEvent {
+        plot_id: live_plot.0,
+    });
+}
+
+fn turn_terminal(time: Res<Time>, mut terminals: Query<&mut Transform, With<Terminal>>) {
+    for mut transform in terminals.iter_mut() {
+        transform.rotation = Quat::from_rotation_y(0.5 * (time.elapsed_secs() * 0.5).sin());
+    }
+}
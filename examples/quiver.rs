@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.set_bounds(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0));
+
+    // flow field sampled over the visible range of the axes
+    plot.quiveropt_func(vortex, vec![Opt::ColorByMagnitude(true), Opt::NumPoints(16)]);
+
+    // steering forces given at explicit positions
+    let positions = vec![Vec2::new(-1.5, 1.5), Vec2::new(0.0, 1.5), Vec2::new(1.5, 1.5)];
+    let forces = vec![Vec2::new(0.3, -0.2), Vec2::new(0.0, -0.4), Vec2::new(-0.3, -0.2)];
+    plot.quiveropt(
+        positions,
+        forces,
+        vec![Opt::Color(Color::srgb(0.9, 0.3, 0.3)), Opt::ArrowScale(1.0), Opt::Size(1.5)],
+    );
+
+    plots.add(plot.clone());
+}
+
+pub fn vortex(x: f32, y: f32) -> Vec2 {
+    Vec2::new(-y, x) * (-(x * x + y * y) / 2.0).exp()
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+
+    // the font is needed for the axis names and the legend
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    commands.insert_resource(TickLabelFont { maybe_font: Some(font) });
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+    plot.tick_label_color = Color::WHITE;
+
+    plot.radaropt(
+        vec!["Strength", "Agility", "Intellect", "Stamina", "Charisma", "Luck"],
+        vec![
+            ("Warrior", vec![9.0, 5.0, 2.0, 8.0, 4.0, 3.0]),
+            ("Rogue", vec![4.0, 9.0, 5.0, 4.0, 6.0, 8.0]),
+            ("Mage", vec![2.0, 4.0, 10.0, 3.0, 7.0, 5.0]),
+        ],
+        vec![Opt::MaxValue(10.0), Opt::Rings(5)],
+    );
+
+    plots.add(plot.clone());
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // resource composition over time
+    let xs = (0..50).map(|i| i as f32).collect::<Vec<f32>>();
+    let wood = xs.iter().map(|x| 10.0 + 5.0 * (x * 0.2).sin()).collect::<Vec<f32>>();
+    let stone = xs.iter().map(|x| 5.0 + x * 0.3).collect::<Vec<f32>>();
+    let gold = xs.iter().map(|x| 2.0 + 2.0 * (x * 0.1).cos().abs()).collect::<Vec<f32>>();
+
+    plot.stacked_areaopt(xs, vec![wood, stone, gold], vec![Opt::Normalize(true)]);
+
+    plots.add(plot.clone());
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // one sample per simulation tick
+    let ticks = (0..25)
+        .map(|tick| Vec2::new(tick as f32, (tick as f32 * 0.5).sin() * 2.0))
+        .collect::<Vec<Vec2>>();
+
+    plot.stemopt(
+        ticks.clone(),
+        vec![
+            Opt::Color(Color::srgb(0.6, 0.6, 0.9)),
+            Opt::MarkerColor(Color::srgb(0.9, 0.8, 0.2)),
+            Opt::MarkerStyle(MarkerStyle::Square),
+            Opt::MarkerSize(0.8),
+        ],
+    );
+
+    // the same signal held until the next tick
+    plot.plotopt(ticks, vec![Opt::Step(StepMode::Post)]);
+
+    plots.add(plot.clone());
+}
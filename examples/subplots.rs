@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (1000., 700.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, mut layout: ResMut<PlotLayout>) {
+    commands.spawn(Camera2d::default());
+
+    let signal = (0..40)
+        .map(|i| Vec2::new(i as f32 * 0.25, (i as f32 * 0.25).sin()))
+        .collect::<Vec<Vec2>>();
+
+    let mut stems = Plot::default();
+    stems.stem(signal);
+
+    let mut field = Plot::default();
+    field.set_bounds(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0));
+    field.contouropt(
+        ContourField::Function(|x, y| x * x - y * y),
+        vec![-3.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0],
+        vec![Opt::Fill(true)],
+    );
+
+    let mut arrows = Plot::default();
+    arrows.set_bounds(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0));
+    arrows.quiver_func(|x, y| Vec2::new(-y, x));
+
+    // the canvas sizes and positions are computed by the layout, and updated when the window is resized
+    let mut figure = Figure::new(2, 2);
+    figure.title_height = 20.0;
+    figure
+        .add_span(plots.add(stems), 0, 0, 1, 2)
+        .add(plots.add(field), 1, 0)
+        .add(plots.add(arrows), 1, 1);
+
+    layout.add(figure);
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .add_systems(Update, jump_to_lap)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>, asset_server: Res<AssetServer>) {
+    commands.spawn(Camera2d::default());
+
+    // the font is needed for the text of the tooltips
+    let font: Handle<Font> = asset_server.load("fonts/Roboto-Bold.ttf");
+    commands.insert_resource(TickLabelFont { maybe_font: Some(font) });
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    let lap_times = (0..20)
+        .map(|lap| Vec2::new(lap as f32, 80.0 + 5.0 * (lap as f32 * 0.8).sin()))
+        .collect::<Vec<Vec2>>();
+
+    plot.stemopt(
+        lap_times,
+        vec![Opt::Name("lap times".to_string()), Opt::Baseline(70.0)],
+    );
+
+    // hover a stem to see the lap and its time
+    let unit = "s".to_string();
+    plot.tooltip_formatter = Some(TooltipFormatter::new(move |point| {
+        format!("lap {}: {:.2} {}", point.index + 1, point.position.y, unit)
+    }));
+
+    plots.add(plot.clone());
+}
+
+// clicking on a stem could, for instance, jump the replay to the start of the lap
+fn jump_to_lap(mut clicked_event: EventReader<PlotPointClicked>) {
+    for event in clicked_event.read() {
+        println!("jumping to lap {} ({:.2} s)", event.index + 1, event.position.y);
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (800., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    plot.canvas_size = Vec2::new(790.0, 590.0);
+
+    // temperature in degrees, on the left axis
+    let temperature = (0..24)
+        .map(|hour| Vec2::new(hour as f32, 15.0 + 8.0 * ((hour as f32 - 9.0) * 0.26).sin()))
+        .collect::<Vec<Vec2>>();
+
+    // electricity consumption in kWh, on the right axis
+    let consumption = (0..24)
+        .map(|hour| Vec2::new(hour as f32 + 0.3, 600.0 + 300.0 * ((hour as f32 - 3.0) * 0.52).cos()))
+        .collect::<Vec<Vec2>>();
+
+    // plotopt does not fit the range to the data: the secondary axis fits itself to the first
+    // series bound to it
+    plot.set_bounds(Vec2::new(-1.0, 5.0), Vec2::new(24.5, 25.0));
+
+    plot.plotopt(
+        temperature,
+        vec![Opt::Color(Color::srgb(0.9, 0.5, 0.3)), Opt::Size(1.5)],
+    );
+
+    plot.plotopt(
+        consumption,
+        vec![
+            Opt::YAxis(YAxis::Right),
+            Opt::Color(Color::srgb(0.3, 0.6, 0.9)),
+            Opt::MarkerColor(Color::srgb(0.3, 0.6, 0.9)),
+            Opt::MarkerStyle(MarkerStyle::Square),
+        ],
+    );
+
+    if let Some(axis) = plot.secondary_axis.as_mut() {
+        axis.formatter = Some(|kwh| format!("{:.0} kWh", kwh));
+        axis.label_color = Color::srgb(0.3, 0.6, 0.9);
+    }
+
+    plots.add(plot.clone());
+}
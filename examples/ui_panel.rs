@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use bevy_plot::*;
+
+// cargo run --example ui_panel --features ui
+fn main() {
+    App::new()
+        .add_plugins((
+            PlotPlugin,
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "I am a window!".into(),
+                    name: Some("bevy.app".into()),
+                    resolution: (900., 600.).into(),
+
+                    ..default()
+                }),
+                ..default()
+            }),
+        ))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut plots: ResMut<PlotMap>) {
+    commands.spawn(Camera2d::default());
+
+    let mut plot = Plot::default();
+    let xs = (0..100).map(|i| i as f32 * 0.1).collect::<Vec<f32>>();
+    plot.stemopt(
+        xs.iter()
+            .map(|x| Vec2::new(*x, x.sin()))
+            .collect::<Vec<Vec2>>(),
+        vec![Opt::Name("sin".to_string())],
+    );
+    plot.set_bounds(Vec2::new(-0.5, -1.5), Vec2::new(10.5, 1.5));
+
+    // the canvas is rendered offscreen, away from anything drawn in the world
+    plot.canvas_position = Vec2::new(-5000.0, 0.0);
+    let plot_id = plots.add(plot);
+
+    // a side menu and a panel holding the plot, which takes all the remaining space
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            padding: UiRect::all(Val::Px(20.0)),
+            column_gap: Val::Px(20.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(200.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.2, 0.25)),
+            ));
+
+            parent
+                .spawn((
+                    Node {
+                        flex_grow: 1.0,
+                        padding: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.3, 0.35)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        PlotNode::new(plot_id, 1),
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                    ));
+                });
+        });
+}
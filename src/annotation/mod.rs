@@ -0,0 +1,148 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes and labels of the annotations, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct AnnotationPart;
+
+/// Spans and rectangle fills are drawn with this fraction of the opacity of the annotation color
+const FILL_OPACITY: f32 = 0.25;
+
+pub(crate) fn annotation_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    maybe_font: Res<TickLabelFont>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<AnnotationPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        // clamped points stay one pixel inside the inner area, where labels are still shown
+        let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+        let inner = half_size - 1.0;
+        let font_size = 14.0;
+        let margin = 4.0;
+
+        let mut spans = ShapeMeshBuilder::default();
+        let mut lines = ShapeMeshBuilder::default();
+        let mut labels: Vec<(String, Vec2, Anchor, Color)> = vec![];
+
+        for annotation in plot.data.annotations.iter() {
+            let to_local = |v: Vec2| {
+                let local = plot.to_local_axis(v, annotation.y_axis);
+                if annotation.clamp {
+                    local.clamp(-inner, inner)
+                } else {
+                    local
+                }
+            };
+
+            let color = annotation.color;
+            let fill_color = color.with_alpha(color.alpha() * FILL_OPACITY);
+            let thickness = annotation.size * 2.0;
+
+            // where the label goes, if any
+            let label_position = match &annotation.annotation {
+                Annotation::Text(position, text) => {
+                    labels.push((text.clone(), to_local(*position), Anchor::Center, color));
+                    None
+                }
+
+                Annotation::Arrow(start, end) => {
+                    let (start, end) = (to_local(*start), to_local(*end));
+                    lines.arrow(start, end, thickness, color);
+
+                    // the label is written behind the tail of the arrow
+                    let dir = (end - start).normalize_or_zero();
+                    Some((start - dir * font_size, Anchor::Center))
+                }
+
+                Annotation::HLine(y) => {
+                    let y = to_local(Vec2::new(plot.bounds.lo.x, *y)).y;
+                    lines.line(Vec2::new(-half_size.x, y), Vec2::new(half_size.x, y), thickness, color);
+                    Some((Vec2::new(half_size.x - margin, y + margin), Anchor::BottomRight))
+                }
+
+                Annotation::VLine(x) => {
+                    let x = to_local(Vec2::new(*x, plot.bounds.lo.y)).x;
+                    lines.line(Vec2::new(x, -half_size.y), Vec2::new(x, half_size.y), thickness, color);
+                    Some((Vec2::new(x + margin, half_size.y - margin), Anchor::TopLeft))
+                }
+
+                Annotation::XSpan(lo, up) => {
+                    let lo = to_local(Vec2::new(*lo, plot.bounds.lo.y)).x;
+                    let up = to_local(Vec2::new(*up, plot.bounds.lo.y)).x;
+                    let (lo, up) = (lo.min(up), lo.max(up));
+                    spans.rect(Vec2::new(lo, -half_size.y), Vec2::new(up, half_size.y), fill_color);
+                    Some((Vec2::new(lo + margin, half_size.y - margin), Anchor::TopLeft))
+                }
+
+                Annotation::YSpan(lo, up) => {
+                    let lo = to_local(Vec2::new(plot.bounds.lo.x, *lo)).y;
+                    let up = to_local(Vec2::new(plot.bounds.lo.x, *up)).y;
+                    let (lo, up) = (lo.min(up), lo.max(up));
+                    spans.rect(Vec2::new(-half_size.x, lo), Vec2::new(half_size.x, up), fill_color);
+                    Some((Vec2::new(-half_size.x + margin, up - margin), Anchor::TopLeft))
+                }
+
+                Annotation::Rect(lo, up) => {
+                    let (a, b) = (to_local(*lo), to_local(*up));
+                    let (lo, up) = (a.min(b), a.max(b));
+
+                    if annotation.fill {
+                        spans.rect(lo, up, fill_color);
+                    }
+
+                    let corners = [lo, Vec2::new(up.x, lo.y), up, Vec2::new(lo.x, up.y), lo];
+                    lines.polyline(&corners, thickness, color);
+                    Some((Vec2::new(lo.x + margin, up.y - margin), Anchor::TopLeft))
+                }
+            };
+
+            if let (Some(label), Some((position, anchor))) = (annotation.label.as_ref(), label_position) {
+                labels.push((label.clone(), position, anchor, color));
+            }
+        }
+
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            spans,
+            1.03,
+            AnnotationPart,
+        );
+
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            lines,
+            1.15,
+            AnnotationPart,
+        );
+
+        if let Some(font) = maybe_font.maybe_font.as_ref() {
+            for (text, position, anchor, color) in labels {
+                spawn_shape_label(
+                    &mut commands,
+                    plot,
+                    &text,
+                    position,
+                    1.16,
+                    font_size,
+                    color,
+                    font,
+                    anchor,
+                    AnnotationPart,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes of the candlestick plots, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct CandlestickPart;
+
+fn spawn_candles(builder: &mut ShapeMeshBuilder, plot: &Plot, candlestick: &CandlestickData) {
+    let candles = &candlestick.data;
+
+    // the width of the candles is based on the shortest time between two candles
+    let min_dt = candles
+        .windows(2)
+        .map(|pair| pair[1].time - pair[0].time)
+        .filter(|dt| *dt > 0.0)
+        .fold(f32::INFINITY, f32::min);
+    let min_dt = if min_dt.is_finite() { min_dt } else { 1.0 };
+    let half_width = min_dt * candlestick.size / 2.0;
+
+    let wick_thickness = 1.5;
+
+    let inner_half_height = plot.canvas_size.y / (1.0 + plot.outer_border.y) / 2.0;
+    let strip_height = 2.0 * inner_half_height * candlestick.volume_height;
+    let max_volume = candles.iter().filter_map(|c| c.volume).fold(0.0, f32::max);
+
+    for candle in candles.iter() {
+        let color = if candle.close >= candle.open {
+            candlestick.up_color
+        } else {
+            candlestick.down_color
+        };
+
+        // wick
+        let high = plot.to_local_axis(Vec2::new(candle.time, candle.high), candlestick.y_axis);
+        let low = plot.to_local_axis(Vec2::new(candle.time, candle.low), candlestick.y_axis);
+        builder.line(low, high, wick_thickness, color);
+
+        // body, at least one pixel high so that a doji remains visible
+        let mut lo = plot.to_local_axis(
+            Vec2::new(candle.time - half_width, candle.open.min(candle.close)),
+            candlestick.y_axis,
+        );
+        let mut up = plot.to_local_axis(
+            Vec2::new(candle.time + half_width, candle.open.max(candle.close)),
+            candlestick.y_axis,
+        );
+        if up.y - lo.y < 1.0 {
+            lo.y -= 0.5;
+            up.y += 0.5;
+        }
+        builder.rect(lo, up, color);
+
+        if let (true, Some(volume)) = (candlestick.show_volume, candle.volume) {
+            if max_volume > 0.0 {
+                let bar_height = volume / max_volume * strip_height * 0.9;
+                builder.rect(
+                    Vec2::new(lo.x, -inner_half_height),
+                    Vec2::new(up.x, -inner_half_height + bar_height),
+                    color.with_alpha(0.5),
+                );
+            }
+        }
+    }
+}
+
+pub(crate) fn candlestick_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<CandlestickPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        for candlestick in plot.data.candlestick_groups.iter() {
+            let mut builder = ShapeMeshBuilder::default();
+            spawn_candles(&mut builder, plot, candlestick);
+
+            spawn_shape_mesh(
+                &mut commands,
+                &mut meshes,
+                &mut shape_materials,
+                plot,
+                builder,
+                1.07,
+                CandlestickPart,
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use bevy::prelude::*;
+
+use crate::canvas::*;
+use crate::inputs::*;
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Added to a canvas while the user drags a selection rectangle over it.
+#[derive(Component)]
+pub(crate) struct BoxZoom {
+    /// Position of the cursor when the drag started, in `World` coordinates
+    pub start: Vec2,
+}
+
+/// Marks the mesh of the selection rectangle.
+#[derive(Component)]
+pub(crate) struct BoxZoomPart;
+
+/// Axes affected by a box zoom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BoxZoomMode {
+    Both,
+    XOnly,
+    YOnly,
+}
+
+impl BoxZoomMode {
+    fn from_keys(keyboard_input: &ButtonInput<KeyCode>, input_map: &PlotInputMap) -> Self {
+        if input_map.shortcut_pressed(keyboard_input, input_map.box_zoom_x_only) {
+            BoxZoomMode::XOnly
+        } else if input_map.shortcut_pressed(keyboard_input, input_map.box_zoom_y_only) {
+            BoxZoomMode::YOnly
+        } else {
+            BoxZoomMode::Both
+        }
+    }
+}
+
+/// Corners of the selection in the local reference frame of the canvas, clamped to its inner area.
+fn selection(plot: &Plot, start: Vec2, end: Vec2, mode: BoxZoomMode) -> (Vec2, Vec2) {
+    let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+    let a = (start - plot.canvas_position).clamp(-half_size, half_size);
+    let b = (end - plot.canvas_position).clamp(-half_size, half_size);
+
+    let mut lo = a.min(b);
+    let mut up = a.max(b);
+
+    match mode {
+        BoxZoomMode::Both => {}
+        BoxZoomMode::XOnly => {
+            lo.y = -half_size.y;
+            up.y = half_size.y;
+        }
+        BoxZoomMode::YOnly => {
+            lo.x = -half_size.x;
+            up.x = half_size.x;
+        }
+    }
+
+    (lo, up)
+}
+
+/// Converts a point in the local reference frame of the canvas to plot coordinates.
+fn local_to_plot(plot: &Plot, local: Vec2) -> Vec2 {
+    let size = plot.canvas_size / (1.0 + plot.outer_border);
+    plot.bounds.lo + (local / size + 0.5) * (plot.bounds.up - plot.bounds.lo)
+}
+
+/// Draws the selection rectangle while dragging, and zooms onto it when the mouse button is released.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_box_zoom(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    mut plots: ResMut<PlotMap>,
+    cursor: Res<Cursor>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_map: Res<PlotInputMap>,
+    canvas_query: Query<(Entity, &PlotIdComponent, &MeshMaterial2d<CanvasMaterial>, &BoxZoom)>,
+    part_query: Query<(Entity, &PlotIdComponent), With<BoxZoomPart>>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+    mut axes_changed_event: EventWriter<AxesChangedEvent>,
+) {
+    for (canvas_entity, plot_id_comp, material_handle, box_zoom) in canvas_query.iter() {
+        let plot_id = plot_id_comp.0;
+
+        for (entity, part_plot_id) in part_query.iter() {
+            if part_plot_id.0 == plot_id {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        let Some(plot) = plots.get_mut(&plot_id) else {
+            continue;
+        };
+
+        let mode = BoxZoomMode::from_keys(&keyboard_input, &input_map);
+        let (lo, up) = selection(plot, box_zoom.start, cursor.position, mode);
+
+        if !mouse_button_input.pressed(input_map.primary_button) {
+            commands.entity(canvas_entity).remove::<BoxZoom>();
+
+            // a simple click, or a selection that is too thin, does not zoom
+            if (up - lo).min_element() < 4.0 {
+                continue;
+            }
+
+            let previous = plot.view_state();
+            plot.zoom_to_region(local_to_plot(plot, lo), local_to_plot(plot, up));
+            plot.record_view(previous);
+
+            update_plot_labels_event.send(UpdatePlotLabelsEvent { plot_id, canvas_entity });
+            update_target_labels_event.send(UpdateTargetLabelEvent {
+                plot_id,
+                canvas_entity,
+                canvas_material_handle: material_handle.clone(),
+            });
+            respawn_all_event.send(RespawnAllEvent { plot_id });
+            axes_changed_event.send(AxesChangedEvent { plot_id });
+
+            continue;
+        }
+
+        let color = Color::srgba(0.2, 0.4, 0.9, 1.0);
+        let thickness = 1.5;
+        let corners = [lo, Vec2::new(up.x, lo.y), up, Vec2::new(lo.x, up.y)];
+
+        let mut builder = ShapeMeshBuilder::default();
+        builder.rect(lo, up, color.with_alpha(0.15));
+        for k in 0..4 {
+            builder.line(corners[k], corners[(k + 1) % 4], thickness, color);
+        }
+
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            builder,
+            1.22,
+            BoxZoomPart,
+        );
+    }
+}
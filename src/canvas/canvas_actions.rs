@@ -10,14 +10,18 @@ use crate::canvas::*;
 use crate::inputs::*;
 
 use crate::canvas::RespawnAllEvent;
+use crate::shapes::*;
 use crate::util::*;
 
 // use crate::bezier::*;
 use crate::plot::*;
 
+use std::collections::{HashMap, HashSet};
+
 fn spawn_axis_tick_labels(
     commands: &mut Commands,
     // asset_server: &Res<AssetServer>,
+    plot_id: PlotId,
     plot_entity: Entity,
     text: &str,
     font_size: f32,
@@ -58,27 +62,161 @@ fn spawn_axis_tick_labels(
                 // text: Text::from_section(, text_font.clone()).with_alignment(text_alignment),
                 Transform::from_translation(position),
                 anchor,
+                PlotIdComponent(plot_id),
             ))
             .insert(PlotLabel);
     });
 }
 
+/// Maximum distance in pixels between the cursor and a pinned target for the target to be removed
+const PIN_PICK_RADIUS: f32 = 15.0;
+
+fn coordinates_text(plot: &Plot, pos: Vec2) -> String {
+    let str_x = format_numeric_label(plot, pos.x, pos.x > 1000.0 || pos.x < 0.01);
+    let str_y = format_numeric_label(plot, pos.y, pos.y > 1000.0 || pos.y < 0.01);
+
+    format!("({}, {})", str_x, str_y)
+}
+
+/// Differences in x and y, slope and distance from the target at `a` to the target at `b`.
+fn measurement_text(plot: &Plot, a: Vec2, b: Vec2) -> String {
+    let format = |v: f32| format_numeric_label(plot, v, v.abs() > 1000.0 || (v != 0.0 && v.abs() < 0.01));
+
+    let delta = b - a;
+    let slope = if delta.x != 0.0 {
+        format(delta.y / delta.x)
+    } else {
+        "inf".to_string()
+    };
+
+    format!(
+        "dx = {}, dy = {}\nslope = {}, d = {}",
+        format(delta.x),
+        format(delta.y),
+        slope,
+        format(delta.length())
+    )
+}
+
+/// Spawns a cross on each pinned target of the plot, a line between consecutive targets (the last one
+/// being linked to the target following the mouse, if shown) and the measurements along these lines.
+/// Everything is a child of the canvas, in its local reference frame.
+fn spawn_pinned_targets(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    shape_materials: &mut Assets<ShapeMaterial>,
+    plot: &Plot,
+    plot_entity: Entity,
+    maybe_font: Option<&Handle<Font>>,
+) {
+    if plot.pinned_targets.is_empty() {
+        return;
+    }
+
+    let mut chain = plot.pinned_targets.clone();
+    if plot.target_toggle {
+        chain.push(plot.target_position);
+    }
+
+    let pinned_z_plane = 1.19;
+    let cross_size = 6.0;
+    let mut builder = ShapeMeshBuilder::default();
+
+    for pair in chain.windows(2) {
+        builder.line(plot.to_local(pair[0]), plot.to_local(pair[1]), 1.5, plot.target_color);
+    }
+
+    for pin in plot.pinned_targets.iter() {
+        let local = plot.to_local(*pin);
+        builder.line(local - Vec2::X * cross_size, local + Vec2::X * cross_size, 2.0, plot.target_color);
+        builder.line(local - Vec2::Y * cross_size, local + Vec2::Y * cross_size, 2.0, plot.target_color);
+    }
+
+    let mesh = meshes.add(builder.build());
+    let material = shape_materials.add(ShapeMaterial::new(plot));
+
+    let font_size = 14.0;
+    let mut labels = vec![];
+
+    for pin in plot.pinned_targets.iter() {
+        let local = plot.to_local(*pin) + Vec2::splat(font_size * 0.3);
+        labels.push((coordinates_text(plot, *pin), local, bevy::sprite::Anchor::BottomLeft));
+    }
+
+    for pair in chain.windows(2) {
+        let middle = (plot.to_local(pair[0]) + plot.to_local(pair[1])) / 2.0;
+        labels.push((
+            measurement_text(plot, pair[0], pair[1]),
+            middle + Vec2::new(font_size * 0.3, -font_size * 0.3),
+            bevy::sprite::Anchor::TopLeft,
+        ));
+    }
+
+    // labels whose anchor falls outside the inner area of the canvas are not shown
+    let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+    labels.retain(|(_, local, _)| local.x.abs() < half_size.x && local.y.abs() < half_size.y);
+
+    commands.entity(plot_entity).with_children(|parent| {
+        parent.spawn((
+            Mesh2d(mesh),
+            MeshMaterial2d(material),
+            Transform::from_translation(Vec3::Z * pinned_z_plane),
+            PlotIdComponent(plot.id),
+            TargetLabel,
+        ));
+
+        let Some(font) = maybe_font else {
+            return;
+        };
+
+        for (text, local, anchor) in labels {
+            parent.spawn((
+                Text2d::new(text),
+                TextFont {
+                    font: font.clone(),
+                    font_size,
+                    ..default()
+                },
+                TextColor(plot.target_label_color),
+                Transform::from_translation(local.extend(pinned_z_plane + 0.01)),
+                anchor,
+                PlotIdComponent(plot.id),
+                TargetLabel,
+            ));
+        }
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn update_target(
     mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
     // asset_server: Res<AssetServer>,
     maybe_font: Res<TickLabelFont>,
     // mut materials: ResMut<Assets<CanvasMaterial>>,
     mut plots: ResMut<PlotMap>,
     mut update_target_labels_event: EventReader<UpdateTargetLabelEvent>,
-    taget_label_query: Query<Entity, With<TargetLabel>>,
+    taget_label_query: Query<(Entity, &PlotIdComponent), With<TargetLabel>>,
+    canvas_query: Query<(), With<CanvasParams>>,
     // canvas_query: Query<(Entity, &mut Handle<CanvasMaterial>, &Handle<Plot>)>,
     mut canvas_materials: ResMut<Assets<CanvasMaterial>>,
     // font_handle: Res<TickLabelFont>,
     // mut canvas_query: Query<&mut Canvas>,
 ) {
-    if let Some(event) = update_target_labels_event.read().next() {
-        for entity in taget_label_query.iter() {
-            commands.entity(entity).despawn();
+    // If there is a stack of UpdateTargetLabelEvent for the same plot, only read the first one.
+    let mut updated_plots = HashSet::new();
+
+    for event in update_target_labels_event.read() {
+        // the canvas may have been despawned in the meantime, e.g. by a re-layout
+        if !canvas_query.contains(event.canvas_entity) || !updated_plots.insert(event.plot_id) {
+            continue;
+        }
+
+        for (entity, label_plot_id) in taget_label_query.iter() {
+            if label_plot_id.0 == event.plot_id {
+                commands.entity(entity).despawn();
+            }
         }
 
         // let graph_sprite = canvas_query.get_mut(event.canvas_entity).unwrap();
@@ -95,12 +233,7 @@ pub(crate) fn update_target(
                 let target_text_z_plane = 1.2;
                 let font_size = 16.0;
 
-                let pos = plot.target_position;
-
-                let target_str_x = format_numeric_label(&plot, pos.x, pos.x > 1000.0 || pos.x < 0.01);
-                let target_str_y = format_numeric_label(&plot, pos.y, pos.y > 1000.0 || pos.y < 0.01);
-
-                let target_str = format!("({}, {})", target_str_x, target_str_y);
+                let target_str = coordinates_text(plot, plot.target_position);
 
                 let offset = font_size * 0.2;
                 let mut target_position =
@@ -142,6 +275,7 @@ pub(crate) fn update_target(
                                     TextColor(plot.target_label_color),
                                     Transform::from_translation(target_position + offset),
                                     bevy::sprite::Anchor::BottomLeft,
+                                    PlotIdComponent(plot_id),
                                 ))
                                 .insert(TargetLabel);
                         });
@@ -151,6 +285,17 @@ pub(crate) fn update_target(
                 }
             }
 
+            if plot.show_target {
+                spawn_pinned_targets(
+                    &mut commands,
+                    &mut meshes,
+                    &mut shape_materials,
+                    plot,
+                    plot_entity,
+                    maybe_font.maybe_font.as_ref(),
+                );
+            }
+
             // update canvas shader
             if let Some(canvas_mat) = canvas_materials.get_mut(&event.canvas_material_handle) {
                 canvas_mat.update_all(&plot);
@@ -166,25 +311,36 @@ pub(crate) fn update_plot_labels(
     // mut materials: ResMut<Assets<CanvasMaterial>>,
     mut plots: ResMut<PlotMap>,
     mut update_plot_labels_event: EventReader<UpdatePlotLabelsEvent>,
-    plot_label_query: Query<Entity, With<PlotLabel>>,
+    plot_label_query: Query<(Entity, &PlotIdComponent), With<PlotLabel>>,
     mut canvas_query: Query<&mut CanvasParams>,
 ) {
-    // If there is a stack of UpdatePlotLabelsEvent, only read the first one.
+    // If there is a stack of UpdatePlotLabelsEvent for the same plot, only read the first one.
     if let Some(font_handle) = maybe_font.maybe_font.as_ref() {
-        if let Some(event) = update_plot_labels_event.read().next() {
+        let mut updated_plots = HashSet::new();
+
+        for event in update_plot_labels_event.read() {
+            // the canvas may have been despawned in the meantime, e.g. by a re-layout
+            let Ok(graph_sprite) = canvas_query.get_mut(event.canvas_entity) else {
+                continue;
+            };
+
+            if !updated_plots.insert(event.plot_id) {
+                continue;
+            }
+
             let plot_id = event.plot_id.clone();
 
             // if let Some(plot) = materials.get_mut(plot_handle.clone()) {
 
             if let Some(plot) = plots.get_mut(&plot_id.clone()) {
                 if !plot.hide_tick_labels {
-                    for entity in plot_label_query.iter() {
-                        commands.entity(entity).despawn();
+                    for (entity, label_plot_id) in plot_label_query.iter() {
+                        if label_plot_id.0 == plot_id {
+                            commands.entity(entity).despawn();
+                        }
                     }
                     let plot_entity = event.canvas_entity;
 
-                    let graph_sprite = canvas_query.get_mut(event.canvas_entity).unwrap();
-
                     let size = graph_sprite.original_size;
 
                     let font_size = 16.0;
@@ -267,6 +423,7 @@ pub(crate) fn update_plot_labels(
                                 spawn_axis_tick_labels(
                                     &mut commands,
                                     // &asset_server,
+                                    plot_id,
                                     plot_entity,
                                     &x_str,
                                     font_size,
@@ -346,6 +503,7 @@ pub(crate) fn update_plot_labels(
                                 spawn_axis_tick_labels(
                                     &mut commands,
                                     // &asset_server,
+                                    plot_id,
                                     plot_entity,
                                     &y_str,
                                     font_size,
@@ -360,6 +518,47 @@ pub(crate) fn update_plot_labels(
                             }
                         }
                     }
+
+                    ////////////////////////////// secondary y_axis labels //////////////////////////////
+                    if let Some(axis) = plot.secondary_axis.as_ref() {
+                        let center_dist_x = graph_x / 2.0 - font_size * 0.20;
+                        let iter_y = y_edge * 2.0 / (axis.up - axis.lo);
+
+                        let bottom_y = (axis.lo / axis.tick_period).ceil() as i64;
+                        let top_y = (axis.up / axis.tick_period).floor() as i64;
+
+                        let max_abs_y = (axis.tick_period * bottom_y as f32)
+                            .abs()
+                            .max((axis.tick_period * top_y as f32).abs());
+
+                        for i in bottom_y..top_y + 1 {
+                            if plot.hide_half_ticks && (i % 2).abs() == 1 {
+                                continue;
+                            }
+
+                            let value = i as f32 * axis.tick_period;
+                            let y_str = match axis.formatter {
+                                Some(formatter) => formatter(value),
+                                None => format_numeric_label(plot, value, !(0.01..1000.0).contains(&max_abs_y)),
+                            };
+
+                            let y_pos = -y_edge + iter_y * (value - axis.lo);
+
+                            if (y_pos + graph_y / 2.0) > font_size * 1.2 && (y_pos - graph_y / 2.0) < 0.0 {
+                                spawn_axis_tick_labels(
+                                    &mut commands,
+                                    plot_id,
+                                    plot_entity,
+                                    &y_str,
+                                    font_size,
+                                    Vec2::new(center_dist_x, y_pos).extend(0.0001),
+                                    bevy::sprite::Anchor::TopRight,
+                                    axis.label_color,
+                                    font_handle,
+                                );
+                            }
+                        }
+                    }
                 }
             }
         }
@@ -443,7 +642,7 @@ pub(crate) fn spawn_graph(
         }
     }
 }
-fn format_numeric_label(plot: &Plot, label: f32, scientific_notation: bool) -> String {
+pub(crate) fn format_numeric_label(plot: &Plot, label: f32, scientific_notation: bool) -> String {
     // scientific notation if the numbers are larger than 1000
     // if max_abs_y >= 1000.0 || max_abs_y < 0.01 {
     if scientific_notation {
@@ -467,9 +666,10 @@ pub(crate) fn update_mouse_target(
 
     cursor: Res<Cursor>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_map: Res<PlotInputMap>,
     mut plots: ResMut<PlotMap>,
 ) {
-    if mouse_button_input.pressed(MouseButton::Middle) {
+    if mouse_button_input.pressed(input_map.target_button) {
         for (canvas_entity, mut canvas_material, plot_id_component) in &mut canvas_query {
             // println!("{:?}", "CHANGING SHADER");
             // if let Some(plot) = my_canvas_mat.get_mut(plot_handle) {
@@ -477,10 +677,16 @@ pub(crate) fn update_mouse_target(
             let plot_id = plot_id_component.0;
             let plot = plots.plots.get_mut(&plot_id).unwrap();
 
+            if !plot.interaction.target || cursor.hovered_plot != Some(plot_id) {
+                continue;
+            }
+
             if let Some(canvas_material_instance) = my_canvas_mats.get_mut(&canvas_material.0) {
                 plot.compute_zeros();
 
-                if mouse_button_input.just_pressed(MouseButton::Middle) {
+                if mouse_button_input.just_pressed(input_map.target_button)
+                    && canvas_material_instance.within_rect(cursor.position)
+                {
                     // println!("target_toggle");
                     plot.target_toggle = !plot.target_toggle;
                 }
@@ -521,9 +727,68 @@ pub(crate) fn update_mouse_target(
     }
 }
 
+/// Pins a target at the cursor or removes the pinned target under it, and redraws the pinned targets
+/// of the plots whose targets have changed, whether with the keys or from code.
+pub(crate) fn update_pinned_targets(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<PlotInputMap>,
+    cursor: Res<Cursor>,
+    mut plots: ResMut<PlotMap>,
+    canvas_query: Query<(Entity, &CanvasParams, &PlotIdComponent, &MeshMaterial2d<CanvasMaterial>)>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut drawn_targets: Local<HashMap<Entity, Vec<Vec2>>>,
+) {
+    let pin = input_map.shortcut_just_pressed(&keyboard_input, input_map.pin_target_key);
+    let unpin = input_map.shortcut_just_pressed(&keyboard_input, input_map.unpin_target_key);
+
+    drawn_targets.retain(|entity, _| canvas_query.contains(*entity));
+
+    for (canvas_entity, graph_sprite, plot_id_comp, material_handle) in canvas_query.iter() {
+        let Some(plot) = plots.get_mut(&plot_id_comp.0) else {
+            continue;
+        };
+
+        let hovered = cursor.hovered_plot == Some(plot_id_comp.0);
+        if plot.interaction.target && hovered && graph_sprite.within_rect(cursor.position) {
+            if pin {
+                let position = plot.world_to_plot(cursor.position);
+                plot.pin_target(position);
+            }
+
+            if unpin {
+                let closest = plot
+                    .pinned_targets
+                    .iter()
+                    .map(|pin| (plot.to_local(*pin) + plot.canvas_position).distance(cursor.position))
+                    .enumerate()
+                    .filter(|(_, distance)| *distance < PIN_PICK_RADIUS)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+                if let Some((index, _)) = closest {
+                    plot.unpin_target(index);
+                }
+            }
+        }
+
+        // a respawned canvas is a new entity, on which the targets are drawn again
+        if drawn_targets.get(&canvas_entity) == Some(&plot.pinned_targets) {
+            continue;
+        }
+
+        drawn_targets.insert(canvas_entity, plot.pinned_targets.clone());
+
+        update_target_labels_event.send(UpdateTargetLabelEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+            canvas_material_handle: material_handle.clone(),
+        });
+    }
+}
+
 pub(crate) fn change_plot(
     mut commands: Commands,
     mut my_plots: ResMut<PlotMap>,
+    layout: Res<PlotLayout>,
     canvas_query: Query<(
         Entity,
         &CanvasParams,
@@ -532,47 +797,108 @@ pub(crate) fn change_plot(
     )>,
 
     keyboard_input: Res<ButtonInput<KeyCode>>,
-    cursor: Res<Cursor>,
+    mut cursor: ResMut<Cursor>,
     mut mouse_motion_events: EventReader<MouseMotion>,
     mut mouse_wheel_events: EventReader<MouseWheel>,
     mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_map: Res<PlotInputMap>,
+    time: Res<Time>,
 
     mut release_all_event: EventWriter<ReleaseAllEvent>,
     mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
     mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
-    window: Single<Entity, With<Window>>,
 ) {
-    for (canvas_entity, graph_sprite, plot_id, canvas_material_handle) in canvas_query.iter() {
-        //
+    for (_, _, plot_id, _) in canvas_query.iter() {
         if let Some(plot) = my_plots.get_mut(&plot_id.0) {
             plot.plot_coord_mouse_pos = plot.world_to_plot(cursor.position);
+        }
+    }
 
-            graph_sprite.hovered_on_plot_edges(cursor.position, *window, &mut commands);
+    // the mouse events are read once per frame, whatever the number of canvases
+    let mouse_delta = mouse_motion_events.read().fold(Vec2::ZERO, |acc, event| acc + event.delta);
+    // some platforms turn vertical scrolling into horizontal scrolling while Shift is held
+    let wheel_delta = mouse_wheel_events
+        .read()
+        .map(|event| if event.y != 0.0 { event.y } else { event.x })
+        .sum::<f32>();
+
+    // only the canvas under the cursor (or with one of its corners under the cursor) receives the inputs.
+    // The offscreen canvases of the plots rendered to a texture can only be reached through a plot node
+    let reachable = |plot_id: PlotId| match cursor.node_plot {
+        Some(node_plot) => plot_id == node_plot,
+        None => my_plots.get(&plot_id).is_some_and(|plot| plot.render_target.is_none()),
+    };
 
-            for event in mouse_motion_events.read() {
-                //
-                // When pressing P and moving the mouse, the tick period changes
-                if keyboard_input.pressed(KeyCode::KeyP) {
-                    plot.tick_period.x *= 1.0 + (event.delta.x) / 1000.0;
-                    plot.tick_period.y *= 1.0 + (event.delta.y) / 1000.0;
+    let hovered = canvas_query
+        .iter()
+        .filter(|(_, _, plot_id, _)| reachable(plot_id.0))
+        .filter(|(_, graph_sprite, _, _)| graph_sprite.distance_to(cursor.position) < graph_sprite.hover_radius)
+        .min_by(|(_, a, _, _), (_, b, _, _)| {
+            a.distance_to(cursor.position)
+                .partial_cmp(&b.distance_to(cursor.position))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
 
-                    plot.clamp_tick_period();
+    cursor.hovered_plot = hovered.map(|(_, _, plot_id, _)| plot_id.0);
 
-                    update_plot_labels_event.send(UpdatePlotLabelsEvent {
-                        plot_id: plot_id.0,
-                        canvas_entity,
-                    });
-                    update_target_labels_event.send(UpdateTargetLabelEvent {
-                        plot_id: plot_id.0,
-                        canvas_entity,
-                        canvas_material_handle: canvas_material_handle.clone(),
-                    });
-                }
-            }
+    let Some((canvas_entity, graph_sprite, plot_id, canvas_material_handle)) = hovered else {
+        cursor.reset_icon(&mut commands);
+
+        if mouse_button_input.just_released(input_map.primary_button) {
+            release_all_event.send(ReleaseAllEvent);
+        }
+        return;
+    };
+
+    if let Some(plot) = my_plots.get_mut(&plot_id.0) {
+        let mut interaction = plot.interaction;
+
+        // the canvases of a figure or anchored to the window are placed by the layout, which would undo the change
+        if layout.manages(plot_id.0) {
+            interaction.drag = false;
+            interaction.resize = false;
+        }
+
+        let move_modifier = keyboard_input.any_pressed(input_map.move_canvas_modifiers.iter().copied());
+        let drag_canvas = interaction.drag && move_modifier && graph_sprite.within_rect(cursor.position);
+
+        if drag_canvas {
+            cursor.set_icon(&mut commands, SystemCursorIcon::Move);
+        } else if interaction.resize {
+            graph_sprite.hovered_on_plot_edges(&mut cursor, &mut commands);
+        } else {
+            cursor.set_icon(&mut commands, SystemCursorIcon::Default);
+        }
+
+        // When pressing Alt+P and moving the mouse, the tick period changes
+        if interaction.tick_period
+            && input_map.shortcut_pressed(&keyboard_input, input_map.tick_period_key)
+            && mouse_delta != Vec2::ZERO
+        {
+            plot.tick_period.x *= 1.0 + (mouse_delta.x) / 1000.0;
+            plot.tick_period.y *= 1.0 + (mouse_delta.y) / 1000.0;
+
+            plot.clamp_tick_period();
+
+            update_plot_labels_event.send(UpdatePlotLabelsEvent {
+                plot_id: plot_id.0,
+                canvas_entity,
+            });
+            update_target_labels_event.send(UpdateTargetLabelEvent {
+                plot_id: plot_id.0,
+                canvas_entity,
+                canvas_material_handle: canvas_material_handle.clone(),
+            });
+        }
+
+        if interaction.zoom && graph_sprite.within_rect(cursor.position) {
+            // the wheel steps of the frame are applied at once, since the component holds a single zoom
+            if wheel_delta != 0.0 {
+                plot.push_zoom_view(time.elapsed_secs());
 
-            for wheel_event in mouse_wheel_events.read() {
                 commands.entity(canvas_entity).insert(ZoomAxes {
-                    wheel_dir: wheel_event.y,
+                    wheel_dir: wheel_delta,
+                    axes: input_map.wheel_zoom_axes(&keyboard_input),
                     mouse_pos: cursor.position,
                 });
 
@@ -587,19 +913,34 @@ pub(crate) fn change_plot(
                     canvas_material_handle: canvas_material_handle.clone(),
                 });
             }
+        }
 
-            if mouse_button_input.just_pressed(MouseButton::Left) {
-                //
-                if graph_sprite.within_rect(cursor.position) {
-                    commands.entity(canvas_entity).insert(MoveAxes);
+        if mouse_button_input.just_pressed(input_map.primary_button) {
+            // grabbing the canvas itself, or one of its corners or edges, takes precedence over the axes
+            if drag_canvas {
+                commands.entity(canvas_entity).insert(MoveCanvas {
+                    previous_position: graph_sprite.position,
+                });
+            } else if interaction.resize && graph_sprite.resize_handle_at(cursor.position).is_some() {
+                graph_sprite.clicked_on_plot_edges(cursor.position, &mut commands, canvas_entity);
+            } else if graph_sprite.within_rect(cursor.position) {
+                // holding Shift while dragging selects a region to zoom onto instead of moving the axes
+                let box_zoom_modifier = keyboard_input.any_pressed(input_map.box_zoom_modifiers.iter().copied());
+
+                if interaction.box_zoom && box_zoom_modifier {
+                    commands.entity(canvas_entity).insert(BoxZoom {
+                        start: cursor.position,
+                    });
+                } else if interaction.pan && !box_zoom_modifier {
+                    commands.entity(canvas_entity).insert(MoveAxes {
+                        start: plot.view_state(),
+                    });
                 }
-
-                graph_sprite.clicked_on_plot_corner(cursor.position, &mut commands, canvas_entity);
             }
         }
     }
 
-    if mouse_button_input.just_released(MouseButton::Left) {
+    if mouse_button_input.just_released(input_map.primary_button) {
         release_all_event.send(ReleaseAllEvent);
     }
 }
@@ -607,106 +948,194 @@ pub(crate) fn change_plot(
 pub(crate) fn release_all(
     mut commands: Commands,
     mut query2: Query<(Entity, &mut CanvasParams), With<ResizePlotWindow>>,
-    query3: Query<Entity, With<MoveAxes>>,
+    query3: Query<(Entity, &MoveAxes, &PlotIdComponent)>,
+    query4: Query<Entity, With<MoveCanvas>>,
+    mut plots: ResMut<PlotMap>,
     mut release_all_event: EventReader<ReleaseAllEvent>,
-    mut window_entity: Single<Entity, With<Window>>,
+    mut cursor: ResMut<Cursor>,
 ) {
     for _ in release_all_event.read() {
         for (entity, mut graph_sprite) in query2.iter_mut() {
             commands.entity(entity).remove::<ResizePlotWindow>();
             graph_sprite.previous_scale = graph_sprite.scale;
         }
-        for entity in query3.iter() {
+        for (entity, move_axes, plot_id) in query3.iter() {
             commands.entity(entity).remove::<MoveAxes>();
+            if let Some(plot) = plots.get_mut(&plot_id.0) {
+                plot.record_view(move_axes.start.clone());
+            }
+        }
+        for entity in query4.iter() {
+            commands.entity(entity).remove::<MoveCanvas>();
         }
         // let window = window.get_primary_mut().unwrap();
         // window.set_cursor_icon(CursorIcon::Default);
-        commands
-            .entity(*window_entity)
-            .insert(CursorIcon::System(SystemCursorIcon::Default));
+        cursor.reset_icon(&mut commands);
+    }
+}
+
+/// Size and position of a canvas whose `handle` has been dragged by `delta` pixels. The opposite corner
+/// or edge stays in place, unless the aspect ratio is locked while dragging an edge, in which case the
+/// canvas grows on both sides of the other axis.
+fn resized_canvas(plot: &Plot, resize: &ResizePlotWindow, delta: Vec2) -> (Vec2, Vec2) {
+    let sides = resize.handle.sides();
+    let previous_size = resize.previous_size;
+
+    let mut size = (previous_size + delta * sides).max(Vec2::ONE);
+
+    if plot.lock_aspect {
+        let ratio = if sides.x != 0.0 && sides.y != 0.0 {
+            (size / previous_size).max_element()
+        } else if sides.x != 0.0 {
+            size.x / previous_size.x
+        } else {
+            size.y / previous_size.y
+        };
+        size = previous_size * ratio;
+
+        // the minimum size is reached without changing the aspect ratio
+        size *= (plot.min_canvas_size / size).max_element().max(1.0);
+    } else {
+        size = size.max(plot.min_canvas_size);
     }
+
+    let position = resize.previous_position + (size - previous_size) / 2.0 * sides;
+
+    (position, size)
 }
 
+/// Moves and resizes the canvas to the given rectangle. The canvas is never scaled: its mesh is rebuilt at
+/// the new size (see [`adjust_graph_size`]) and everything drawn on it is respawned at native scale, so that
+/// the text stays crisp and the lines keep their thickness.
+fn place_canvas(
+    plot: &mut Plot,
+    graph_sprite: &mut CanvasParams,
+    transform: &mut Transform,
+    canvas_material: &mut CanvasMaterial,
+    position: Vec2,
+    size: Vec2,
+) {
+    transform.translation.x = position.x;
+    transform.translation.y = position.y;
+    transform.scale = Vec3::ONE;
+
+    graph_sprite.position = position;
+    graph_sprite.original_size = size;
+    graph_sprite.scale = Vec2::ONE;
+    graph_sprite.previous_scale = Vec2::ONE;
+
+    plot.canvas_position = position;
+    plot.canvas_size = size;
+    plot.compute_zeros();
+
+    canvas_material.position = position;
+    canvas_material.size = size;
+}
+
+/// Resizes the canvases whose corners or edges are dragged: the quad of the canvas is rebuilt at the new
+/// size, the outer border keeps the same width in pixels on both axes, the shape materials of the plot are
+/// clipped to the new inner area, and the tick labels, the targets and the series are respawned.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn adjust_graph_size(
-    mut canvas_query: Query<
-        (
-            Entity,
-            &mut CanvasParams,
-            &PlotIdComponent,
-            &MeshMaterial2d<CanvasMaterial>,
-            &ResizePlotWindow,
-            &mut Transform,
-        ),
-        Without<Locked>,
-    >,
+    mut canvas_query: Query<(
+        Entity,
+        &mut CanvasParams,
+        &PlotIdComponent,
+        &MeshMaterial2d<CanvasMaterial>,
+        &Mesh2d,
+        &ResizePlotWindow,
+        &mut Transform,
+    )>,
+    shape_query: Query<(&PlotIdComponent, &MeshMaterial2d<ShapeMaterial>)>,
     mut my_canvas_mat: ResMut<Assets<CanvasMaterial>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    mut meshes: ResMut<Assets<Mesh>>,
     mut plots: ResMut<PlotMap>,
     cursor: Res<Cursor>,
     mut update_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
 ) {
-    for (canvas_entity, mut graph_sprite, plot_id_comp, mat_handle, resize_corner, mut transform) in &mut canvas_query {
-        //
+    for (canvas_entity, mut graph_sprite, plot_id_comp, mat_handle, mesh, resize, mut transform) in &mut canvas_query {
+        let (Some(canvas_material), Some(plot)) = (my_canvas_mat.get_mut(mat_handle), plots.get_mut(&plot_id_comp.0))
+        else {
+            continue;
+        };
 
-        if let Some(canvas_material) = my_canvas_mat.get_mut(mat_handle) {
-            let delta = cursor.pos_relative_to_click;
-            let mut new_transform_scale;
-
-            match resize_corner.corner {
-                Corner::TopLeft => {
-                    new_transform_scale = Vec3::new(
-                        resize_corner.previous_scale.x - delta.x / 350.0,
-                        resize_corner.previous_scale.y + delta.y / 350.0,
-                        1.0,
-                    );
-                }
-                Corner::TopRight => {
-                    let delta = cursor.pos_relative_to_click;
-
-                    new_transform_scale = Vec3::new(
-                        resize_corner.previous_scale.x + delta.x / 350.0,
-                        resize_corner.previous_scale.y + delta.y / 350.0,
-                        1.0,
-                    );
-                }
+        let (position, size) = resized_canvas(plot, resize, cursor.pos_relative_to_click);
 
-                Corner::BottomRight => {
-                    let delta = cursor.pos_relative_to_click;
+        if position == graph_sprite.position && size == graph_sprite.original_size * graph_sprite.scale {
+            continue;
+        }
 
-                    new_transform_scale = Vec3::new(
-                        resize_corner.previous_scale.x + delta.x / 350.0,
-                        resize_corner.previous_scale.y - delta.y / 350.0,
-                        1.0,
-                    );
-                }
-                Corner::BottomLeft => {
-                    let delta = cursor.pos_relative_to_click;
-
-                    new_transform_scale = Vec3::new(
-                        resize_corner.previous_scale.x - delta.x / 350.0,
-                        resize_corner.previous_scale.y - delta.y / 350.0,
-                        1.0,
-                    );
+        // same relation between the two borders as in Plot::new
+        plot.outer_border.x = plot.outer_border.y * size.y / size.x;
+        canvas_material.outer_border = plot.outer_border;
+
+        place_canvas(plot, &mut graph_sprite, &mut transform, canvas_material, position, size);
+
+        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+            *mesh = Mesh::from(Rectangle::from_size(size));
+        }
+
+        // the shapes that are only respawned in a later frame clip to the new inner area meanwhile
+        for (shape_plot_id, shape_material) in shape_query.iter() {
+            if shape_plot_id.0 == plot_id_comp.0 {
+                if let Some(shape_material) = shape_materials.get_mut(shape_material) {
+                    *shape_material = ShapeMaterial::new(plot);
                 }
             }
+        }
 
-            new_transform_scale.x = new_transform_scale.x.clamp(0.1, 10.0);
-            new_transform_scale.y = new_transform_scale.y.clamp(0.1, 10.0);
+        update_labels_event.send(UpdatePlotLabelsEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+        });
 
-            transform.scale = new_transform_scale;
+        update_target_labels_event.send(UpdateTargetLabelEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+            canvas_material_handle: mat_handle.clone(),
+        });
 
-            graph_sprite.scale = transform.scale.truncate();
+        respawn_all_event.send(RespawnAllEvent {
+            plot_id: plot_id_comp.0,
+        });
+    }
+}
 
-            canvas_material.size = graph_sprite.scale * graph_sprite.original_size;
+/// Moves the canvases dragged while holding one of the [`PlotInputMap::move_canvas_modifiers`].
+pub(crate) fn drag_canvas(
+    mut canvas_query: Query<(
+        &mut CanvasParams,
+        &PlotIdComponent,
+        &MeshMaterial2d<CanvasMaterial>,
+        &MoveCanvas,
+        &mut Transform,
+    )>,
+    mut my_canvas_mat: ResMut<Assets<CanvasMaterial>>,
+    mut plots: ResMut<PlotMap>,
+    cursor: Res<Cursor>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+) {
+    for (mut graph_sprite, plot_id_comp, mat_handle, move_canvas, mut transform) in &mut canvas_query {
+        let (Some(canvas_material), Some(plot)) = (my_canvas_mat.get_mut(mat_handle), plots.get_mut(&plot_id_comp.0))
+        else {
+            continue;
+        };
+
+        let position = move_canvas.previous_position + cursor.pos_relative_to_click;
+        if position == graph_sprite.position {
+            continue;
+        }
 
-            update_labels_event.send(UpdatePlotLabelsEvent {
-                plot_id: plot_id_comp.0,
-                canvas_entity,
-            });
+        let size = graph_sprite.original_size * graph_sprite.scale;
+        place_canvas(plot, &mut graph_sprite, &mut transform, canvas_material, position, size);
 
-            if let Some(plot) = plots.get_mut(&plot_id_comp.0) {
-                plot.canvas_size = canvas_material.size;
-            }
-        }
+        // the labels and targets are children of the canvas and follow it, but not the series
+        respawn_all_event.send(RespawnAllEvent {
+            plot_id: plot_id_comp.0,
+        });
     }
 }
 
@@ -715,7 +1144,7 @@ pub(crate) fn adjust_graph_axes(
     mut query: ParamSet<(
         Query<
             (Entity, &CanvasParams, &PlotIdComponent, &MeshMaterial2d<CanvasMaterial>),
-            (With<MoveAxes>, Without<Locked>),
+            With<MoveAxes>,
         >,
         Query<
             (
@@ -725,7 +1154,6 @@ pub(crate) fn adjust_graph_axes(
                 &MeshMaterial2d<CanvasMaterial>,
                 &ZoomAxes,
             ),
-            Without<Locked>,
         >,
     )>,
 
@@ -735,6 +1163,7 @@ pub(crate) fn adjust_graph_axes(
     mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
     mut change_canvas_material_event: EventWriter<RespawnAllEvent>,
     mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut axes_changed_event: EventWriter<AxesChangedEvent>,
     mut canvas_materials: ResMut<Assets<CanvasMaterial>>,
     // mut spawn_beziercurve_event: EventWriter<SpawnBezierCurveEvent>,
 ) {
@@ -771,6 +1200,10 @@ pub(crate) fn adjust_graph_axes(
                     // canvas_material_handle: material_handle.clone(),
                 });
 
+                axes_changed_event.send(AxesChangedEvent {
+                    plot_id: plot_id_comp.0,
+                });
+
                 // plot.data.bezier_groups.iter().enumerate().for_each(|(k, _)| {
                 //     let bezier_curve = plot.data.bezier_groups.get(k).unwrap();
                 //     // So as to not spawn twice when show_animation is turned on
@@ -790,7 +1223,7 @@ pub(crate) fn adjust_graph_axes(
         //
         if let Some(plot) = plots.get_mut(&plot_id_comp.0) {
             //
-            plot.zoom_axes(zoom_info.wheel_dir);
+            plot.zoom_axes(zoom_info.wheel_dir, zoom_info.axes);
 
             plot.clamp_tick_period();
 
@@ -825,5 +1258,62 @@ pub(crate) fn adjust_graph_axes(
             plot_id: plot_id_comp.0,
             // canvas_material_handle: material_handle.clone(),
         });
+
+        axes_changed_event.send(AxesChangedEvent {
+            plot_id: plot_id_comp.0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resize(handle: ResizeHandle) -> ResizePlotWindow {
+        ResizePlotWindow {
+            handle,
+            previous_position: Vec2::ZERO,
+            previous_size: Vec2::new(400.0, 200.0),
+        }
+    }
+
+    #[test]
+    fn opposite_corner_stays_in_place() {
+        let plot = Plot::default();
+
+        let (position, size) = resized_canvas(&plot, &resize(ResizeHandle::BottomRight), Vec2::new(50.0, -20.0));
+        assert_eq!(size, Vec2::new(450.0, 220.0));
+        // the top left corner is still at (-200, 100)
+        assert_eq!(position, Vec2::new(25.0, -10.0));
+    }
+
+    #[test]
+    fn canvas_does_not_shrink_below_its_minimum_size() {
+        let plot = Plot::default();
+
+        let (position, size) = resized_canvas(&plot, &resize(ResizeHandle::Left), Vec2::new(500.0, 0.0));
+        assert_eq!(size, Vec2::new(plot.min_canvas_size.x, 200.0));
+        // the right edge is still at x = 200
+        assert_eq!(position.x + size.x / 2.0, 200.0);
+    }
+
+    #[test]
+    fn locked_aspect_grows_both_sides_of_the_other_axis() {
+        let mut plot = Plot::default();
+        plot.lock_aspect = true;
+
+        let (position, size) = resized_canvas(&plot, &resize(ResizeHandle::Right), Vec2::new(100.0, 0.0));
+        assert_eq!(size, Vec2::new(500.0, 250.0));
+        assert_eq!(position, Vec2::new(50.0, 0.0));
+    }
+
+    #[test]
+    fn locked_aspect_keeps_the_ratio_at_the_minimum_size() {
+        let mut plot = Plot::default();
+        plot.lock_aspect = true;
+
+        let (_, size) = resized_canvas(&plot, &resize(ResizeHandle::TopRight), Vec2::new(-390.0, -190.0));
+        assert_eq!(size.x / size.y, 2.0);
+        assert!(size.cmpge(plot.min_canvas_size).all(), "{:?}", size);
     }
 }
@@ -1,6 +1,9 @@
 // pub mod canvas;
+pub mod box_zoom;
 pub mod canvas_actions;
 #[allow(unused_imports)]
+pub use box_zoom::*;
+#[allow(unused_imports)]
 pub use canvas_actions::*;
 
 // use bevy::{
@@ -16,6 +19,7 @@ pub use canvas_actions::*;
 //     sprite::{Material2d, Material2dPipeline, Material2dPlugin},
 // };
 
+use crate::inputs::Cursor;
 use crate::plot::*;
 use crate::util::*;
 use bevy::prelude::*;
@@ -24,7 +28,6 @@ use bevy::render::render_resource::AsBindGroup;
 use bevy::render::render_resource::*;
 use bevy::sprite::{Material2d, Material2dPlugin};
 use bevy::window::SystemCursorIcon;
-use bevy::winit::cursor::CursorIcon;
 
 #[derive(Component)]
 pub(crate) struct PlotLabel;
@@ -38,20 +41,59 @@ pub(crate) struct SpawnGraphEvent {
     pub canvas: CanvasParams,
 }
 
-pub(crate) enum Corner {
+/// Corner or edge of a canvas grabbed to resize it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ResizeHandle {
     TopLeft,
     TopRight,
     BottomLeft,
     BottomRight,
+    Top,
+    Bottom,
+    Left,
+    Right,
 }
 
-// TODO: unimplemented
+impl ResizeHandle {
+    /// Sides of the canvas moved by the handle: -1 for the left or bottom side, 1 for the right or
+    /// top side and 0 if the size does not change along the axis.
+    pub(crate) fn sides(&self) -> Vec2 {
+        match self {
+            ResizeHandle::TopLeft => Vec2::new(-1.0, 1.0),
+            ResizeHandle::TopRight => Vec2::new(1.0, 1.0),
+            ResizeHandle::BottomLeft => Vec2::new(-1.0, -1.0),
+            ResizeHandle::BottomRight => Vec2::new(1.0, -1.0),
+            ResizeHandle::Top => Vec2::new(0.0, 1.0),
+            ResizeHandle::Bottom => Vec2::new(0.0, -1.0),
+            ResizeHandle::Left => Vec2::new(-1.0, 0.0),
+            ResizeHandle::Right => Vec2::new(1.0, 0.0),
+        }
+    }
+
+    fn cursor_icon(&self) -> SystemCursorIcon {
+        match self {
+            ResizeHandle::TopLeft => SystemCursorIcon::NwResize,
+            ResizeHandle::TopRight => SystemCursorIcon::NeResize,
+            ResizeHandle::BottomLeft => SystemCursorIcon::SwResize,
+            ResizeHandle::BottomRight => SystemCursorIcon::SeResize,
+            ResizeHandle::Top | ResizeHandle::Bottom => SystemCursorIcon::NsResize,
+            ResizeHandle::Left | ResizeHandle::Right => SystemCursorIcon::EwResize,
+        }
+    }
+}
+
+/// Present on a canvas while one of its corners or edges is dragged.
 #[derive(Component)]
 pub(crate) struct ResizePlotWindow {
-    pub corner: Corner,
-    #[allow(dead_code)]
+    pub handle: ResizeHandle,
+    pub previous_position: Vec2,
+    pub previous_size: Vec2,
+}
+
+/// Present on a canvas while it is dragged to a new position.
+#[derive(Component)]
+pub(crate) struct MoveCanvas {
     pub previous_position: Vec2,
-    pub previous_scale: Vec2,
 }
 
 #[derive(Component, Clone)]
@@ -78,106 +120,79 @@ impl CanvasParams {
         return false;
     }
 
-    pub(crate) fn clicked_on_plot_corner(&self, position: Vec2, commands: &mut Commands, entity: Entity) {
-        let size = self.original_size * self.scale;
-        let top_right = self.position + Vec2::new(size.x / 2.0, size.y / 2.0);
-        let bottom_left = self.position + Vec2::new(-size.x / 2.0, -size.y / 2.0);
-        let top_left = self.position + Vec2::new(-size.x / 2.0, size.y / 2.0);
-        let bottom_right = self.position + Vec2::new(size.x / 2.0, -size.y / 2.0);
-
-        // println!("{:?}", position);
-        // println!("top_left: {:?}", top_left);
-
-        if (top_right - position).length() < self.hover_radius {
-            commands.entity(entity).insert(ResizePlotWindow {
-                corner: Corner::TopRight,
-                // previous_size: size,
-                previous_position: self.position,
-                previous_scale: self.scale,
-            });
-        }
+    /// Distance from `position` to the canvas, zero if `position` is inside the canvas.
+    pub(crate) fn distance_to(&self, position: Vec2) -> f32 {
+        let half_size = self.original_size * self.scale / 2.0;
+        let outside = ((position - self.position).abs() - half_size).max(Vec2::ZERO);
+        outside.length()
+    }
 
-        if (bottom_left - position).length() < self.hover_radius {
-            commands.entity(entity).insert(ResizePlotWindow {
-                corner: Corner::BottomLeft,
-                // previous_size: size,
-                previous_position: self.position,
-                previous_scale: self.scale,
-            });
+    /// Corner or edge of the canvas under `position`, if any. Corners are grabbed within `hover_radius`
+    /// and edges within a third of it.
+    pub(crate) fn resize_handle_at(&self, position: Vec2) -> Option<ResizeHandle> {
+        let half_size = self.original_size * self.scale / 2.0;
+        let local = position - self.position;
+
+        let corners = [
+            (Vec2::new(-1.0, 1.0), ResizeHandle::TopLeft),
+            (Vec2::new(1.0, 1.0), ResizeHandle::TopRight),
+            (Vec2::new(-1.0, -1.0), ResizeHandle::BottomLeft),
+            (Vec2::new(1.0, -1.0), ResizeHandle::BottomRight),
+        ];
+
+        for (corner, handle) in corners {
+            if (corner * half_size - local).length() < self.hover_radius {
+                return Some(handle);
+            }
         }
 
-        if (top_left - position).length() < self.hover_radius {
-            commands.entity(entity).insert(ResizePlotWindow {
-                corner: Corner::TopLeft,
-                // previous_size: self.size,
-                previous_position: self.position,
-                previous_scale: self.scale,
-            });
-            println!("top left");
-        }
+        let edge_width = self.hover_radius / 3.0;
+        let distance_to_edges = (local.abs() - half_size).abs();
 
-        if (bottom_right - position).length() < self.hover_radius {
-            commands.entity(entity).insert(ResizePlotWindow {
-                corner: Corner::BottomRight,
-                // previous_size: self.size,
-                previous_position: self.position,
-                previous_scale: self.scale,
-            });
+        if local.y.abs() < half_size.y && distance_to_edges.x < edge_width {
+            return Some(if local.x > 0.0 { ResizeHandle::Right } else { ResizeHandle::Left });
         }
-    }
 
-    pub(crate) fn hovered_on_plot_edges(&self, position: Vec2, window_entity: Entity, mut commands: &mut Commands) {
-        let size = self.original_size * self.scale;
-
-        let top_right = self.position + Vec2::new(size.x / 2.0, size.y / 2.0);
-        let bottom_left = self.position + Vec2::new(-size.x / 2.0, -size.y / 2.0);
-        let top_left = self.position + Vec2::new(-size.x / 2.0, size.y / 2.0);
-        let bottom_right = self.position + Vec2::new(size.x / 2.0, -size.y / 2.0);
-
-        let mut set_to_default_cursor = true;
-
-        if (top_left - position).length() < self.hover_radius {
-            commands
-                .entity(window_entity)
-                .insert(CursorIcon::System(SystemCursorIcon::NwResize));
-            set_to_default_cursor = false;
+        if local.x.abs() < half_size.x && distance_to_edges.y < edge_width {
+            return Some(if local.y > 0.0 { ResizeHandle::Top } else { ResizeHandle::Bottom });
         }
 
-        if (top_right - position).length() < self.hover_radius {
-            commands
-                .entity(window_entity)
-                .insert(CursorIcon::System(SystemCursorIcon::NeResize));
-            set_to_default_cursor = false;
-        }
+        None
+    }
 
-        if (bottom_left - position).length() < self.hover_radius {
-            commands
-                .entity(window_entity)
-                .insert(CursorIcon::System(SystemCursorIcon::SwResize));
-            set_to_default_cursor = false;
-        }
+    /// Starts resizing the canvas if `position` is on one of its corners or edges.
+    pub(crate) fn clicked_on_plot_edges(&self, position: Vec2, commands: &mut Commands, entity: Entity) {
+        let Some(handle) = self.resize_handle_at(position) else {
+            return;
+        };
 
-        if (bottom_right - position).length() < self.hover_radius {
-            commands
-                .entity(window_entity)
-                .insert(CursorIcon::System(SystemCursorIcon::SeResize));
-            set_to_default_cursor = false;
-        }
+        commands.entity(entity).insert(ResizePlotWindow {
+            handle,
+            previous_position: self.position,
+            previous_size: self.original_size * self.scale,
+        });
+    }
 
-        if set_to_default_cursor {
-            commands
-                .entity(window_entity)
-                .insert(CursorIcon::System(SystemCursorIcon::Default));
-        }
+    pub(crate) fn hovered_on_plot_edges(&self, cursor: &mut Cursor, commands: &mut Commands) {
+        let icon = match self.resize_handle_at(cursor.position) {
+            Some(handle) => handle.cursor_icon(),
+            None => SystemCursorIcon::Default,
+        };
+
+        cursor.set_icon(commands, icon);
     }
 }
 
 #[derive(Component)]
-pub(crate) struct MoveAxes;
+pub(crate) struct MoveAxes {
+    /// View when the axes were grabbed, recorded in the history on release if the axes have moved
+    pub start: ViewState,
+}
 
 #[derive(Component)]
 pub(crate) struct ZoomAxes {
     pub wheel_dir: f32,
+    pub axes: BVec2,
     #[allow(dead_code)]
     pub mouse_pos: Vec2,
 }
@@ -188,6 +203,12 @@ pub(crate) struct UpdatePlotLabelsEvent {
     pub canvas_entity: Entity,
 }
 
+/// Sent after the bounds of a plot have been changed with the mouse (pan or zoom).
+#[derive(Event)]
+pub(crate) struct AxesChangedEvent {
+    pub plot_id: PlotId,
+}
+
 #[derive(Event)]
 pub(crate) struct UpdateTargetLabelEvent {
     pub plot_id: PlotId,
@@ -331,3 +352,48 @@ impl Material2d for CanvasMaterial {
         bevy::sprite::AlphaMode2d::Blend
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 200x100 canvas centered at (100, 50)
+    fn canvas() -> CanvasParams {
+        CanvasParams {
+            position: Vec2::new(100.0, 50.0),
+            previous_position: Vec2::new(100.0, 50.0),
+            original_size: Vec2::new(200.0, 100.0),
+            scale: Vec2::ONE,
+            previous_scale: Vec2::ONE,
+            hover_radius: 15.0,
+        }
+    }
+
+    #[test]
+    fn corners_are_grabbed_within_the_hover_radius() {
+        let canvas = canvas();
+
+        assert_eq!(canvas.resize_handle_at(Vec2::new(0.0, 100.0)), Some(ResizeHandle::TopLeft));
+        assert_eq!(canvas.resize_handle_at(Vec2::new(190.0, 90.0)), Some(ResizeHandle::TopRight));
+        assert_eq!(canvas.resize_handle_at(Vec2::new(-5.0, -5.0)), Some(ResizeHandle::BottomLeft));
+        assert_eq!(canvas.resize_handle_at(Vec2::new(205.0, 0.0)), Some(ResizeHandle::BottomRight));
+    }
+
+    #[test]
+    fn edges_are_grabbed_within_a_third_of_the_hover_radius() {
+        let canvas = canvas();
+
+        assert_eq!(canvas.resize_handle_at(Vec2::new(100.0, 104.0)), Some(ResizeHandle::Top));
+        assert_eq!(canvas.resize_handle_at(Vec2::new(100.0, -4.0)), Some(ResizeHandle::Bottom));
+        assert_eq!(canvas.resize_handle_at(Vec2::new(4.0, 50.0)), Some(ResizeHandle::Left));
+        assert_eq!(canvas.resize_handle_at(Vec2::new(196.0, 50.0)), Some(ResizeHandle::Right));
+
+        assert_eq!(canvas.resize_handle_at(Vec2::new(100.0, 106.0)), None);
+        assert_eq!(canvas.resize_handle_at(Vec2::new(194.0, 50.0)), None);
+    }
+
+    #[test]
+    fn inside_of_the_canvas_has_no_handle() {
+        assert_eq!(canvas().resize_handle_at(Vec2::new(100.0, 50.0)), None);
+    }
+}
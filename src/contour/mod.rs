@@ -0,0 +1,403 @@
+use bevy::prelude::*;
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::canvas::*;
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes and labels of the contour plots, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct ContourPart;
+
+/// Values of a scalar field sampled on a regular grid, in plot coordinates.
+struct SampledField {
+    lo: Vec2,
+    up: Vec2,
+    values: Vec<Vec<f32>>,
+}
+
+impl SampledField {
+    fn new(field: &ContourField, bounds: &PlotCanvasBounds, num_points: usize) -> Self {
+        match field {
+            ContourField::Function(f) => {
+                let delta = (bounds.up - bounds.lo) / num_points as f32;
+                let values = (0..=num_points)
+                    .map(|j| {
+                        (0..=num_points)
+                            .map(|i| {
+                                let p = bounds.lo + Vec2::new(i as f32, j as f32) * delta;
+                                f(p.x, p.y)
+                            })
+                            .collect()
+                    })
+                    .collect();
+
+                SampledField {
+                    lo: bounds.lo,
+                    up: bounds.up,
+                    values,
+                }
+            }
+
+            ContourField::Grid { lo, up, values } => SampledField {
+                lo: *lo,
+                up: *up,
+                values: values.clone(),
+            },
+        }
+    }
+
+    fn num_rows(&self) -> usize {
+        self.values.len()
+    }
+
+    fn num_cols(&self) -> usize {
+        self.values.iter().map(|row| row.len()).min().unwrap_or(0)
+    }
+
+    fn point(&self, i: usize, j: usize) -> Vec2 {
+        let cells = Vec2::new((self.num_cols() - 1).max(1) as f32, (self.num_rows() - 1).max(1) as f32);
+        self.lo + Vec2::new(i as f32, j as f32) * (self.up - self.lo) / cells
+    }
+
+    /// Corners of the cell `(i, j)` in counter-clockwise order, starting with the bottom left one.
+    fn cell(&self, i: usize, j: usize) -> Option<[(Vec2, f32); 4]> {
+        let corners =
+            [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)].map(|(i, j)| (self.point(i, j), self.values[j][i]));
+
+        if corners.iter().all(|(_, v)| v.is_finite()) {
+            Some(corners)
+        } else {
+            None
+        }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = [(Vec2, f32); 4]> + '_ {
+        let num_cols = self.num_cols();
+        let num_rows = self.num_rows();
+        (0..num_rows.saturating_sub(1))
+            .flat_map(move |j| (0..num_cols.saturating_sub(1)).map(move |i| (i, j)))
+            .filter_map(|(i, j)| self.cell(i, j))
+    }
+}
+
+fn interpolate(a: (Vec2, f32), b: (Vec2, f32), level: f32) -> Vec2 {
+    let t = (level - a.1) / (b.1 - a.1);
+    a.0 + (b.0 - a.0) * t
+}
+
+/// Marching squares: segments of the iso-line at `level`, in plot coordinates.
+fn isolines(field: &SampledField, level: f32) -> Vec<[Vec2; 2]> {
+    let mut segments = Vec::new();
+
+    for corners in field.cells() {
+        // crossing points on the bottom, right, top and left edges of the cell
+        let crossings = (0..4)
+            .map(|k| {
+                let a = corners[k];
+                let b = corners[(k + 1) % 4];
+                if (a.1 >= level) != (b.1 >= level) {
+                    Some(interpolate(a, b, level))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<Option<Vec2>>>();
+
+        let points = crossings.iter().flatten().copied().collect::<Vec<Vec2>>();
+
+        match points.len() {
+            2 => segments.push([points[0], points[1]]),
+            4 => {
+                // saddle point: the value at the center of the cell decides which corners are connected
+                let center = corners.iter().map(|(_, v)| v).sum::<f32>() / 4.0;
+                if (center >= level) == (corners[0].1 >= level) {
+                    segments.push([points[0], points[1]]);
+                    segments.push([points[2], points[3]]);
+                } else {
+                    segments.push([points[3], points[0]]);
+                    segments.push([points[1], points[2]]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    segments
+}
+
+/// Joins the segments sharing an end into polylines, so that a dash pattern runs along whole iso-lines.
+/// The segments are expected in pixels: ends closer than a thousandth of a pixel are merged.
+fn chain_segments(segments: &[[Vec2; 2]]) -> Vec<Vec<Vec2>> {
+    let key = |p: Vec2| ((p.x * 1000.0).round() as i64, (p.y * 1000.0).round() as i64);
+
+    let mut ends: HashMap<(i64, i64), Vec<usize>> = HashMap::new();
+    for (k, [a, b]) in segments.iter().enumerate() {
+        ends.entry(key(*a)).or_default().push(k);
+        ends.entry(key(*b)).or_default().push(k);
+    }
+
+    let mut used = vec![false; segments.len()];
+    let mut lines = Vec::new();
+
+    for start in 0..segments.len() {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+
+        let mut line = VecDeque::from(segments[start]);
+
+        // grow the line from its back, then from its front
+        for forward in [true, false] {
+            loop {
+                let end = if forward { line[line.len() - 1] } else { line[0] };
+                let next = ends
+                    .get(&key(end))
+                    .and_then(|candidates| candidates.iter().copied().find(|k| !used[*k]));
+
+                let Some(k) = next else {
+                    break;
+                };
+                used[k] = true;
+
+                let [a, b] = segments[k];
+                let other = if key(a) == key(end) { b } else { a };
+                if forward {
+                    line.push_back(other);
+                } else {
+                    line.push_front(other);
+                }
+            }
+        }
+
+        lines.push(line.into());
+    }
+
+    lines
+}
+
+/// Clips a polygon (with the field value at each vertex) to the half-space where `sign * (value - level) >= 0`.
+fn clip_polygon(polygon: &[(Vec2, f32)], level: f32, sign: f32) -> Vec<(Vec2, f32)> {
+    let mut clipped = Vec::new();
+    let inside = |v: f32| sign * (v - level) >= 0.0;
+
+    for k in 0..polygon.len() {
+        let a = polygon[k];
+        let b = polygon[(k + 1) % polygon.len()];
+
+        if inside(a.1) {
+            clipped.push(a);
+        }
+        if inside(a.1) != inside(b.1) {
+            clipped.push((interpolate(a, b, level), level));
+        }
+    }
+
+    clipped
+}
+
+/// Fills the bands between consecutive levels, including the two unbounded bands below the
+/// lowest level and above the highest one.
+fn fill_bands(builder: &mut ShapeMeshBuilder, plot: &Plot, field: &SampledField, levels: &[f32]) {
+    let num_bands = levels.len() + 1;
+
+    for corners in field.cells() {
+        for triangle in [
+            [corners[0], corners[1], corners[2]],
+            [corners[0], corners[2], corners[3]],
+        ] {
+            for band in 0..num_bands {
+                let mut polygon = triangle.to_vec();
+
+                if band > 0 {
+                    polygon = clip_polygon(&polygon, levels[band - 1], 1.0);
+                }
+                if band < levels.len() {
+                    polygon = clip_polygon(&polygon, levels[band], -1.0);
+                }
+
+                if polygon.len() >= 3 {
+                    let t = band as f32 / (num_bands - 1).max(1) as f32;
+                    let points = polygon.iter().map(|(p, _)| plot.to_local(*p)).collect::<Vec<Vec2>>();
+                    builder.polygon(&points, viridis(t).with_alpha(0.8));
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn contour_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    maybe_font: Res<TickLabelFont>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<ContourPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        for contour in plot.data.contour_groups.iter() {
+            let field = SampledField::new(&contour.field, &plot.bounds, contour.num_points);
+
+            if contour.fill {
+                let mut builder = ShapeMeshBuilder::default();
+                fill_bands(&mut builder, plot, &field, &contour.levels);
+                spawn_shape_mesh(
+                    &mut commands,
+                    &mut meshes,
+                    &mut shape_materials,
+                    plot,
+                    builder,
+                    1.04,
+                    ContourPart,
+                );
+            }
+
+            if contour.line_style == LineStyle::None {
+                continue;
+            }
+
+            let mut builder = ShapeMeshBuilder::default();
+            let thickness = contour.size * 2.0;
+
+            for level in contour.levels.iter() {
+                let segments = isolines(&field, *level);
+
+                let local_segments = segments
+                    .iter()
+                    .map(|[a, b]| [plot.to_local(*a), plot.to_local(*b)])
+                    .collect::<Vec<[Vec2; 2]>>();
+
+                for line in chain_segments(&local_segments) {
+                    builder.styled_polyline(&line, thickness, &contour.line_style, contour.color);
+                }
+
+                // write the level next to the segment in the middle of the list
+                if let (true, Some(font), Some([a, b])) = (
+                    contour.show_labels,
+                    maybe_font.maybe_font.as_ref(),
+                    segments.get(segments.len() / 2),
+                ) {
+                    let max_abs = level.abs();
+                    let label =
+                        format_numeric_label(plot, *level, max_abs >= 1000.0 || (max_abs < 0.01 && max_abs > 0.0));
+
+                    spawn_shape_label(
+                        &mut commands,
+                        plot,
+                        &label,
+                        plot.to_local((*a + *b) / 2.0),
+                        1.06,
+                        14.0,
+                        contour.color,
+                        font,
+                        bevy::sprite::Anchor::Center,
+                        ContourPart,
+                    );
+                }
+            }
+
+            spawn_shape_mesh(
+                &mut commands,
+                &mut meshes,
+                &mut shape_materials,
+                plot,
+                builder,
+                1.05,
+                ContourPart,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_cell(values: [[f32; 2]; 2]) -> SampledField {
+        SampledField {
+            lo: Vec2::ZERO,
+            up: Vec2::ONE,
+            values: values.iter().map(|row| row.to_vec()).collect(),
+        }
+    }
+
+    fn has_segment(segments: &[[Vec2; 2]], a: Vec2, b: Vec2) -> bool {
+        let close = |p: Vec2, q: Vec2| p.distance(q) < 1e-5;
+        segments
+            .iter()
+            .any(|s| (close(s[0], a) && close(s[1], b)) || (close(s[0], b) && close(s[1], a)))
+    }
+
+    #[test]
+    fn saddle_with_high_center_cuts_off_the_low_corners() {
+        // high values on the bottom left and top right corners, center at 0.5
+        let field = unit_cell([[1.0, 0.0], [0.0, 1.0]]);
+        let segments = isolines(&field, 0.5);
+
+        assert_eq!(segments.len(), 2);
+        // bottom right corner
+        assert!(has_segment(&segments, Vec2::new(0.5, 0.0), Vec2::new(1.0, 0.5)));
+        // top left corner
+        assert!(has_segment(&segments, Vec2::new(0.5, 1.0), Vec2::new(0.0, 0.5)));
+    }
+
+    #[test]
+    fn saddle_with_low_center_cuts_off_the_high_corners() {
+        // same corners, but the center (0.3) is below the level
+        let field = unit_cell([[0.6, 0.0], [0.0, 0.6]]);
+        let segments = isolines(&field, 0.5);
+
+        let bottom = interpolate((Vec2::ZERO, 0.6), (Vec2::X, 0.0), 0.5);
+        let left = interpolate((Vec2::ZERO, 0.6), (Vec2::Y, 0.0), 0.5);
+        let right = interpolate((Vec2::ONE, 0.6), (Vec2::X, 0.0), 0.5);
+        let top = interpolate((Vec2::ONE, 0.6), (Vec2::Y, 0.0), 0.5);
+
+        assert_eq!(segments.len(), 2);
+        // bottom left corner
+        assert!(has_segment(&segments, left, bottom));
+        // top right corner
+        assert!(has_segment(&segments, right, top));
+    }
+
+    #[test]
+    fn band_between_two_levels() {
+        // the value grows linearly with x, from 0 to 2
+        let triangle = [
+            (Vec2::new(0.0, 0.0), 0.0),
+            (Vec2::new(2.0, 0.0), 2.0),
+            (Vec2::new(2.0, 2.0), 2.0),
+        ];
+
+        let above_lo = clip_polygon(&triangle, 0.5, 1.0);
+        let band = clip_polygon(&above_lo, 1.5, -1.0);
+
+        assert_eq!(band.len(), 4);
+        for (point, value) in band.iter() {
+            assert!(*value >= 0.5 && *value <= 1.5);
+            assert!(point.x >= 0.5 - 1e-5 && point.x <= 1.5 + 1e-5);
+        }
+
+        // a band that the triangle does not reach is empty
+        assert!(clip_polygon(&triangle, 3.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn segments_are_chained_into_lines() {
+        let segments = [
+            [Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)],
+            [Vec2::new(3.0, 1.0), Vec2::new(2.0, 0.0)],
+            [Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)],
+            [Vec2::new(10.0, 10.0), Vec2::new(11.0, 10.0)],
+        ];
+
+        let mut lines = chain_segments(&segments);
+        lines.sort_by_key(|line| std::cmp::Reverse(line.len()));
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 4);
+        assert_eq!(lines[1].len(), 2);
+    }
+}
@@ -1,10 +1,14 @@
 use bevy::{
     // input::mouse::{MouseMotion, MouseWheel},
     prelude::*,
-    render::camera::OrthographicProjection,
+    render::camera::{NormalizedRenderTarget, OrthographicProjection},
     // sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    window::{PrimaryWindow, SystemCursorIcon},
+    winit::cursor::CursorIcon,
 };
 
+use crate::plot::*;
+
 // use crate::canvas::*;
 // use crate::markers::SpawnMarkersEvent;
 // use crate::util::*;
@@ -14,6 +18,19 @@ pub(crate) struct Cursor {
     pub position: Vec2,
     pub pos_relative_to_click: Vec2,
     pub last_click_position: Vec2,
+
+    /// Window the cursor was last seen in, the primary window until then
+    pub window: Option<Entity>,
+
+    /// Plot whose canvas receives the mouse inputs, if the cursor is over one
+    pub hovered_plot: Option<PlotId>,
+
+    /// Plot of the `PlotNode` under the cursor, whose offscreen canvas is the only one that can be hovered
+    pub node_plot: Option<PlotId>,
+
+    /// Icon set by the plots, if any. Once the cursor leaves the canvases, the icon is reset only once so
+    /// that the icons set by the application are left alone
+    pub icon: Option<SystemCursorIcon>,
 }
 
 impl Default for Cursor {
@@ -22,55 +39,107 @@ impl Default for Cursor {
             position: Vec2::ZERO,
             pos_relative_to_click: Vec2::ZERO,
             last_click_position: Vec2::ZERO,
+            window: None,
+            hovered_plot: None,
+            node_plot: None,
+            icon: None,
         }
     }
 }
 
-pub(crate) fn record_mouse_events_system(
-    mut cursor_moved_events: EventReader<CursorMoved>,
-    mouse_button_input: Res<ButtonInput<MouseButton>>,
-    mut cursor_res: ResMut<Cursor>,
-    // mut windows: ResMut<Windows>,
-    window: Single<&Window>,
-    cam_transform_query: Query<&Transform, With<OrthographicProjection>>,
-    cam_ortho_query: Query<&OrthographicProjection>,
-) {
-    for event in cursor_moved_events.read() {
-        let cursor_in_pixels = event.position; // lower left is origin
+impl Cursor {
+    /// Changes the icon of the cursor in the window it is in.
+    pub fn set_icon(&mut self, commands: &mut Commands, icon: SystemCursorIcon) {
+        if self.icon == Some(icon) {
+            return;
+        }
 
-        let window_size = Vec2::new(window.width(), window.height());
+        if let Some(window) = self.window {
+            // the window may have been closed since
+            commands.entity(window).try_insert(CursorIcon::System(icon));
+        }
+        self.icon = Some(icon);
+    }
 
-        let screen_position = cursor_in_pixels - window_size / 2.0;
+    /// Restores the default icon if it was changed by [`Cursor::set_icon`].
+    pub fn reset_icon(&mut self, commands: &mut Commands) {
+        if self.icon.take().is_some() {
+            if let Some(window) = self.window {
+                commands
+                    .entity(window)
+                    .try_insert(CursorIcon::System(SystemCursorIcon::Default));
+            }
+        }
+    }
+}
+
+/// Position of the cursor in world coordinates, as seen through the given camera. None if the camera
+/// does not render to `window_entity` or if the cursor is outside of its viewport.
+fn cursor_to_world(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    window_entity: Entity,
+    primary_window: Option<Entity>,
+    cursor_in_pixels: Vec2,
+) -> Option<Vec2> {
+    let NormalizedRenderTarget::Window(window_ref) = camera.target.normalize(primary_window)? else {
+        return None;
+    };
 
-        let cam_transform = cam_transform_query.iter().next().unwrap();
+    if !camera.is_active || window_ref.entity() != window_entity {
+        return None;
+    }
 
-        // this variable currently has no effect
-        let mut scale = 1.0;
+    // the position is relative to the viewport of the camera, which may only cover part of the window
+    let viewport_position = match camera.logical_viewport_rect() {
+        Some(viewport) if !viewport.contains(cursor_in_pixels) => return None,
+        Some(viewport) => cursor_in_pixels - viewport.min,
+        None => cursor_in_pixels,
+    };
 
-        for ortho in cam_ortho_query.iter() {
-            scale = ortho.scale;
-        }
+    camera.viewport_to_world_2d(camera_transform, viewport_position).ok()
+}
 
-        // let cursor_vec4: Vec4 = cam_transform.compute_matrix()
-        //     * screen_position.extend(0.0).extend(1.0 / (scale))
-        //     * scale;
+/// Converts the position of the cursor to world coordinates through the camera rendering the plots: the
+/// cameras marked with [`PlotCamera`] if there are any, and any 2d camera otherwise.
+pub(crate) fn record_mouse_events_system(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_map: Res<PlotInputMap>,
+    mut cursor_res: ResMut<Cursor>,
+    windows: Query<(Entity, &Window)>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform, Has<PlotCamera>), With<OrthographicProjection>>,
+) {
+    let primary_window = primary_window.get_single().ok();
+    let has_plot_camera = cameras.iter().any(|(_, _, is_plot_camera)| is_plot_camera);
+
+    // the cursor is converted every frame, since the camera may move while the cursor does not
+    let position = windows.iter().find_map(|(window_entity, window)| {
+        let cursor_in_pixels = window.cursor_position()?; // upper left is origin
 
-        // let cursor_vec4: Mat4 = cam_transform.compute_matrix() * screen_position.extend(0.0).extend(1.0);
-        // let cursor_vec4: Vec4 = cam_transform.compute_matrix() * screen_position.extend(0.0).extend(1.0);
+        cameras
+            .iter()
+            .filter(|(_, _, is_plot_camera)| *is_plot_camera || !has_plot_camera)
+            .find_map(|(camera, camera_transform, _)| {
+                cursor_to_world(camera, camera_transform, window_entity, primary_window, cursor_in_pixels)
+            })
+            .map(|position| (window_entity, position))
+    });
 
-        let cursor_vec4 = cam_transform
-            .compute_matrix()
-            .mul_vec4(screen_position.extend(0.0).extend(1.0 / scale))
-            * scale;
+    if cursor_res.window.is_none() {
+        cursor_res.window = primary_window;
+    }
 
-        let cursor_pos = Vec2::new(cursor_vec4.x, cursor_vec4.y);
-        // let cursor_pos = Vec2::new(cursor_vec4.x, cursor_vec4.y) / cursor_vec4.w;
+    // set again by the plot node under the cursor, if any
+    cursor_res.node_plot = None;
 
-        cursor_res.position = cursor_pos;
+    if let Some((window_entity, position)) = position {
+        cursor_res.window = Some(window_entity);
+        cursor_res.position = position;
         cursor_res.pos_relative_to_click = cursor_res.position - cursor_res.last_click_position;
     }
 
-    if mouse_button_input.just_pressed(MouseButton::Left) {
+    if mouse_button_input.just_pressed(input_map.primary_button) {
         cursor_res.last_click_position = cursor_res.position;
         cursor_res.pos_relative_to_click = Vec2::ZERO;
     }
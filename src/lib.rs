@@ -8,6 +8,29 @@
 //! (e.g. `Vec<Vec2>`, `Vec<(f32, f32)>`, `Vec<f32>`, etc.). In the third option,
 //! `my_function` is an explicit function that takes two arguments (x and time) and returns a `f32`.
 //!
+//! Scalar fields of two variables can be displayed as iso-lines with [`Plot::contour`], either from an
+//! explicit function of x and y or from a grid of values (see [`ContourField`]). Vector fields are
+//! drawn as arrows with [`Plot::quiver`] and [`Plot::quiver_func`], and financial series as candles
+//! with [`Plot::candlestick`] (see [`PlotableOhlc`]). Discrete-time signals can be drawn as staircases
+//! with the [`Opt::Step`] option or as stems with [`Plot::stem`]. Series summing up to a total can be
+//! shown as a stacked area chart with [`Plot::stacked_area`], and shares of a whole as a pie or donut
+//! chart with [`Plot::pie`]. Several stats can be compared on a radar chart with [`Plot::radar`].
+//! A series can be bound to a secondary y axis on the right of the canvas with the [`Opt::YAxis`] option.
+//! Text, arrows, reference lines ([`Plot::axhline`], [`Plot::axvline`]), shaded spans and rectangles
+//! can be laid over the data with [`Plot::annotateopt`] (see [`Annotation`]).
+//!
+//! Instead of positioning each canvas by hand with `canvas_position` and `canvas_size`, several plots
+//! can be arranged in rows and columns with a [`Figure`] added to the [`PlotLayout`] resource, or attached
+//! to a corner or an edge of the window with an [`AnchoredPlot`], sized in pixels or in percentages of the
+//! window. Plots can also share their x and/or y range, so that they pan and zoom together (see [`PlotLinks`]).
+//! The cursor is mapped to the plots through the camera marked with [`PlotCamera`], which may be moved,
+//! scaled or limited to a viewport of the window.
+//! A plot can also be drawn offscreen into an image with [`Plot::render_to_texture`], to be shown on 3d
+//! meshes, sprites or UI images (see examples/plot_texture.rs, and examples/plot_texture_3d.rs with the
+//! `pbr` feature).
+//! With the `ui` feature, a [`PlotNode`] places a plot inside a `bevy_ui` layout, sized by flexbox and
+//! receiving the mouse through the interaction of the node (see examples/ui_panel.rs).
+//!
 //! The following code can be found in examples/minimal.rs:
 //! ```
 //!  use bevy::prelude::*;
@@ -48,17 +71,46 @@
 //! panics if `lo.x > up.x or lo.y > up.y`.
 //!
 //! Note that the library allows the user to
-//! * zoom in and out with the mousewheel,
+//! * zoom in and out with the mousewheel (holding Alt+X or Alt+Y to only zoom one axis),
 //! * move the origin with the mouse by pressing and dragging,
-//! * spawn a target and the corresponding coordinates by pressing the middle mouse button, and
+//! * move the canvas by dragging it while holding Ctrl, and resize it by dragging its corners or edges
+//!   (see [`Plot::min_canvas_size`] and [`Plot::lock_aspect`]),
+//! * zoom onto a region by dragging a rectangle while holding Shift (and Alt+X or Alt+Y to only zoom one axis),
+//! * undo (Alt+U) and redo (Alt+R) these moves, go back to the initial range (Alt+H) or fit the axes to the
+//!   data (Alt+F),
+//! * resume the [`Autoscale`] policy of a plot following live data (Alt+A), which panning or zooming pauses,
+//! * spawn a target and the corresponding coordinates by pressing the middle mouse button,
+//! * pin targets with Alt+T (or [`Plot::pin_target`]) to measure the differences in x and y, the slope and
+//!   the distance between them, and remove the pinned target under the cursor with Alt+Delete,
+//! * hover a data point to see its coordinates in a tooltip (see [`Plot::tooltip_formatter`] and [`TooltipFormatter`]), or click on it
+//!   (see the [`PlotPointClicked`], [`PlotPointHoverEnter`] and [`PlotPointHoverLeave`] events), and
 //! * change the Plot fields at runtime (see examples/runtime_setter.rs).
+//!
+//! The mouse buttons and keys of these interactions can be changed through the [`PlotInputMap`] resource,
+//! and each interaction can be disabled for a given plot through its [`Plot::interaction`] field.
 
 mod plot;
 pub use plot::*;
 
 // mod bezier;
+mod annotation;
+mod candlestick;
 mod canvas;
+mod contour;
 mod inputs;
+mod line;
+mod pie;
+mod quiver;
+mod radar;
 // mod markers;
 // mod segments;
+mod shapes;
+mod stacked_area;
+mod stem;
+mod tooltip;
+#[cfg(feature = "ui")]
+mod ui;
 mod util;
+
+#[cfg(feature = "ui")]
+pub use ui::PlotNode;
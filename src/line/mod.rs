@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes of the regular plots (segments), so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct LinePart;
+
+/// Marks the meshes of the scatter plots (markers), so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct ScatterPart;
+
+/// Draws the series added with [`Plot::plot`] and [`Plot::plotopt`] as polylines, or as staircases in step mode.
+pub(crate) fn line_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<LinePart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        let mut builder = ShapeMeshBuilder::default();
+
+        for segments in plot.data.segment_groups.iter() {
+            let points = segments
+                .points()
+                .iter()
+                .map(|p| plot.to_local_axis(*p, segments.y_axis))
+                .collect::<Vec<Vec2>>();
+
+            builder.styled_polyline(&points, segments.size * 2.0, &segments.line_style, segments.color);
+        }
+
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            builder,
+            1.11,
+            LinePart,
+        );
+    }
+}
+
+/// Draws the markers of the series added with [`Plot::plotm`], or with [`Plot::plotopt`] and a marker option.
+pub(crate) fn scatter_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<ScatterPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        let mut builder = ShapeMeshBuilder::default();
+
+        for markers in plot.data.marker_groups.iter() {
+            let radius = 6.0 * markers.size;
+
+            for point in markers.data.iter() {
+                let center = plot.to_local_axis(*point, markers.y_axis);
+
+                if markers.draw_contour {
+                    builder.marker(center, radius + 1.5, &markers.marker_style, Color::BLACK);
+                }
+                builder.marker(center, radius, &markers.marker_style, markers.color);
+
+                // tiny circle exactly at the data point
+                if markers.marker_style != MarkerStyle::None {
+                    builder.disk(center, (radius * 0.2).max(1.0), markers.marker_point_color);
+                }
+            }
+        }
+
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            builder,
+            1.12,
+            ScatterPart,
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use bevy::{
+    prelude::*,
+    reflect::TypePath,
+    render::render_resource::{AsBindGroup, ShaderRef},
+    sprite::{Material2d, Material2dPlugin},
+    utils::HashMap,
+};
+
+use crate::plot::*;
+use crate::shapes::*;
+use crate::util::*;
+
+pub(crate) struct PieMesh2dPlugin;
+
+impl Plugin for PieMesh2dPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<PieMaterial>::default());
+    }
+}
+
+/// Marks the slices and labels of the pie charts, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct PiePart;
+
+/// One slice of a pie chart, drawn on a quad using the signed distance to an annular sector,
+/// so that the edges remain crisp at any scale.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub(crate) struct PieMaterial {
+    /// Center of the pie in `World` coordinates, including the offset of an exploded slice
+    #[uniform(0)]
+    pub center: Vec2,
+
+    /// Inner and outer radii in pixels
+    #[uniform(0)]
+    pub radii: Vec2,
+
+    /// Start and end angles in radians
+    #[uniform(0)]
+    pub angles: Vec2,
+
+    /// Gap between neighbouring slices in pixels
+    #[uniform(0)]
+    pub gap: f32,
+
+    #[uniform(0)]
+    pub color: Vec4,
+}
+
+impl Material2d for PieMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/pie.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> bevy::sprite::AlphaMode2d {
+        bevy::sprite::AlphaMode2d::Blend
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pie_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut pie_materials: ResMut<Assets<PieMaterial>>,
+    maybe_font: Res<TickLabelFont>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<PiePart>>,
+    mut quad: Local<Option<Handle<Mesh>>>,
+    mut slice_materials: Local<HashMap<PlotId, Vec<Handle<PieMaterial>>>>,
+) {
+    // every slice is the same unit quad scaled by its transform, and keeps the material of the
+    // slice at the same index from the previous respawn, so that respawns do not pile up assets
+    let quad = quad
+        .get_or_insert_with(|| meshes.add(Rectangle::from_size(Vec2::ONE)))
+        .clone();
+    slice_materials.retain(|plot_id, _| plots.get(plot_id).is_some());
+
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        let materials = slice_materials.entry(plot.id).or_default();
+        let mut num_slices = 0;
+
+        let inner_size = plot.canvas_size / (1.0 + plot.outer_border);
+        let total = plot.data.pie_groups.len().max(1) as f32;
+
+        for (k, pie) in plot.data.pie_groups.iter().enumerate() {
+            // several pies share the canvas side by side
+            let width = inner_size.x / total;
+            let center = Vec2::new(-inner_size.x / 2.0 + width * (k as f32 + 0.5), 0.0);
+
+            let max_explode = pie.explode.iter().copied().fold(0.0, f32::max);
+            let label_room = if pie.show_labels || pie.show_percentages {
+                0.7
+            } else {
+                0.9
+            };
+            let radius = label_room * width.min(inner_size.y) / 2.0 / (1.0 + max_explode);
+
+            let sum = pie.values.iter().map(|v| v.max(0.0)).sum::<f32>();
+
+            for (i, (start, end)) in pie.angles().into_iter().enumerate() {
+                if end - start <= 0.0 {
+                    continue;
+                }
+
+                let mid = (start + end) / 2.0;
+                let direction = Vec2::from_angle(mid);
+                let offset = direction * radius * pie.explode.get(i).copied().unwrap_or(0.0);
+                let slice_center = center + offset;
+                let color = pie.colors.get(i).copied().unwrap_or(Color::srgb(0.5, 0.5, 0.5));
+
+                let material = PieMaterial {
+                    center: plot.canvas_position + slice_center,
+                    radii: Vec2::new(radius * pie.inner_radius, radius),
+                    angles: Vec2::new(start, end),
+                    gap: 1.5,
+                    color: col_to_vec4(color),
+                };
+
+                let handle = match materials.get(num_slices) {
+                    Some(handle) => {
+                        if let Some(existing) = pie_materials.get_mut(handle) {
+                            *existing = material;
+                        }
+                        handle.clone()
+                    }
+                    None => {
+                        let handle = pie_materials.add(material);
+                        materials.push(handle.clone());
+                        handle
+                    }
+                };
+                num_slices += 1;
+
+                commands.spawn((
+                    Mesh2d(quad.clone()),
+                    MeshMaterial2d(handle),
+                    Transform::from_translation((plot.canvas_position + slice_center).extend(1.01))
+                        .with_scale(Vec3::new(2.0 * radius + 4.0, 2.0 * radius + 4.0, 1.0)),
+                    PlotIdComponent(plot.id),
+                    PiePart,
+                ));
+
+                let font = if let Some(font) = maybe_font.maybe_font.as_ref() {
+                    font
+                } else {
+                    continue;
+                };
+
+                let name = pie.labels.get(i).filter(|_| pie.show_labels);
+                let percentage = if pie.show_percentages && sum > 0.0 {
+                    Some(format!("{:.1}%", pie.values[i].max(0.0) / sum * 100.0))
+                } else {
+                    None
+                };
+
+                let text = match (name, percentage) {
+                    (Some(name), Some(percentage)) => format!("{} ({})", name, percentage),
+                    (Some(name), None) => name.clone(),
+                    (None, Some(percentage)) => percentage,
+                    (None, None) => continue,
+                };
+
+                let anchor = if direction.x >= 0.0 {
+                    bevy::sprite::Anchor::CenterLeft
+                } else {
+                    bevy::sprite::Anchor::CenterRight
+                };
+
+                spawn_shape_label(
+                    &mut commands,
+                    plot,
+                    &text,
+                    slice_center + direction * (radius + 8.0),
+                    1.015,
+                    16.0,
+                    plot.tick_label_color,
+                    font,
+                    anchor,
+                    PiePart,
+                );
+            }
+        }
+
+        materials.truncate(num_slices);
+    }
+}
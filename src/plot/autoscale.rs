@@ -0,0 +1,341 @@
+use bevy::prelude::*;
+
+use super::plot::*;
+use super::view::*;
+use crate::canvas::*;
+
+/// Policy for the x axis of a plot, applied every frame while the data changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutoscaleX {
+    /// The range only changes with the mouse or with [`Plot::set_bounds`]
+    Fixed,
+    /// Fits the whole data, with margins
+    FitAll,
+    /// Shows the last `width` units of x, so that the view scrolls as data is appended
+    Follow(f32),
+}
+
+/// Policy for the y axes of a plot, applied every frame while the data changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutoscaleY {
+    /// The range only changes with the mouse or with [`Plot::set_bounds`]
+    Fixed,
+    /// Fits the whole data, with margins
+    FitAll,
+    /// Fits the data inside the current x range, with margins
+    FitVisible,
+}
+
+/// Automatic range of the axes of a plot, for instance to follow live data:
+///
+/// ```ignore
+/// plot.autoscale = Autoscale::follow(10.0);
+/// ```
+///
+/// Panning or zooming the plot pauses the policy until the
+/// [`resume_autoscale_key`](crate::PlotInputMap::resume_autoscale_key) is pressed or a [`ViewAction::Resume`] is sent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Autoscale {
+    pub x: AutoscaleX,
+    pub y: AutoscaleY,
+
+    /// Time in seconds the view takes to catch up with the data. Zero jumps to the new range at once
+    pub smoothing: f32,
+
+    /// Set when the user pans or zooms the plot
+    pub paused: bool,
+}
+
+impl Default for Autoscale {
+    fn default() -> Self {
+        Autoscale {
+            x: AutoscaleX::Fixed,
+            y: AutoscaleY::Fixed,
+            smoothing: 0.15,
+            paused: false,
+        }
+    }
+}
+
+impl Autoscale {
+    /// Sliding window over the last `width` units of x, with the y axes fitted to the visible data.
+    pub fn follow(width: f32) -> Self {
+        Autoscale {
+            x: AutoscaleX::Follow(width),
+            y: AutoscaleY::FitVisible,
+            ..Default::default()
+        }
+    }
+
+    /// Both axes fitted to the whole data.
+    pub fn fit_all() -> Self {
+        Autoscale {
+            x: AutoscaleX::FitAll,
+            y: AutoscaleY::FitAll,
+            ..Default::default()
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        !self.paused && (self.x != AutoscaleX::Fixed || self.y != AutoscaleY::Fixed)
+    }
+}
+
+/// Bounds and secondary range the autoscale policy of a plot is heading to.
+#[derive(Debug, Clone, PartialEq)]
+struct AutoscaleTarget {
+    lo: Vec2,
+    up: Vec2,
+    secondary: Option<(f32, f32)>,
+}
+
+/// Smallest span of an axis around values of the magnitude of `lo` and `up`. Below it, the bounds could
+/// collapse to the same `f32`.
+fn min_span(lo: f32, up: f32) -> f32 {
+    (lo.abs().max(up.abs()) * 1e-5).max(f32::EPSILON)
+}
+
+/// Widens the range around its center if it is narrower than [`min_span`].
+fn with_min_span(lo: f32, up: f32) -> (f32, f32) {
+    let span = min_span(lo, up);
+    if up - lo >= span {
+        (lo, up)
+    } else {
+        let center = (lo + up) / 2.0;
+        (center - span / 2.0, center + span / 2.0)
+    }
+}
+
+/// Range in y of the points whose x lies between `lo_x` and `up_x`, with margins.
+fn fit_y(points: &[Vec2], policy: AutoscaleY, lo_x: f32, up_x: f32) -> Option<(f32, f32)> {
+    let (lo, up) = match policy {
+        AutoscaleY::Fixed => return None,
+        AutoscaleY::FitAll => range(points.iter().copied())?,
+        AutoscaleY::FitVisible => range(points.iter().copied().filter(|p| p.x >= lo_x && p.x <= up_x))?,
+    };
+    let (lo, up) = with_margins(lo.y, up.y);
+    Some(with_min_span(lo, up))
+}
+
+fn approach(current: f32, target: f32, t: f32) -> f32 {
+    current + (target - current) * t
+}
+
+impl Plot {
+    fn autoscale_target(&self) -> Option<AutoscaleTarget> {
+        let left = self.series_points(YAxis::Left);
+        let right = match self.secondary_axis {
+            Some(_) => self.series_points(YAxis::Right),
+            None => vec![],
+        };
+
+        let (lo, up) = range(left.iter().chain(right.iter()).copied())?;
+
+        let (lo_x, up_x) = match self.autoscale.x {
+            AutoscaleX::Fixed => (self.bounds.lo.x, self.bounds.up.x),
+            AutoscaleX::FitAll => {
+                let (lo_x, up_x) = with_margins(lo.x, up.x);
+                with_min_span(lo_x, up_x)
+            }
+            AutoscaleX::Follow(width) => {
+                let width = width.abs().max(min_span(up.x, up.x));
+                (up.x - width, up.x + width * 0.05)
+            }
+        };
+
+        let (lo_y, up_y) =
+            fit_y(&left, self.autoscale.y, lo_x, up_x).unwrap_or((self.bounds.lo.y, self.bounds.up.y));
+
+        let secondary = self
+            .secondary_axis
+            .as_ref()
+            .map(|axis| fit_y(&right, self.autoscale.y, lo_x, up_x).unwrap_or((axis.lo, axis.up)));
+
+        Some(AutoscaleTarget {
+            lo: Vec2::new(lo_x, lo_y),
+            up: Vec2::new(up_x, up_y),
+            secondary,
+        })
+    }
+
+    /// Moves the axes towards the range chosen by the autoscale policy. Returns true if the range changed.
+    fn step_autoscale(&mut self, dt: f32) -> bool {
+        if !self.autoscale.is_active() {
+            return false;
+        }
+
+        let Some(target) = self.autoscale_target() else {
+            return false;
+        };
+
+        let current = AutoscaleTarget {
+            lo: self.bounds.lo,
+            up: self.bounds.up,
+            secondary: self.secondary_axis.as_ref().map(|axis| (axis.lo, axis.up)),
+        };
+
+        if current == target {
+            return false;
+        }
+
+        let t = if self.autoscale.smoothing > 0.0 {
+            1.0 - (-dt / self.autoscale.smoothing).exp()
+        } else {
+            1.0
+        };
+
+        // close enough: land exactly on the target so that the plot stops being respawned
+        let tolerance = (target.up - target.lo) * 1e-3;
+        let arrived = (current.lo - target.lo).abs().cmplt(tolerance).all()
+            && (current.up - target.up).abs().cmplt(tolerance).all();
+
+        let (lo, up) = if arrived || t >= 1.0 {
+            (target.lo, target.up)
+        } else {
+            (current.lo.lerp(target.lo, t), current.up.lerp(target.up, t))
+        };
+
+        if let (Some((lo, up)), Some((target_lo, target_up))) = (current.secondary, target.secondary) {
+            let (lo, up) = if arrived || t >= 1.0 {
+                (target_lo, target_up)
+            } else {
+                (approach(lo, target_lo, t), approach(up, target_up, t))
+            };
+            let (lo, up) = with_min_span(lo, up);
+            self.set_secondary_bounds(lo, up);
+        }
+
+        // the interpolation between two valid ranges can still round to an empty one
+        let (lo_x, up_x) = with_min_span(lo.x, up.x);
+        let (lo_y, up_y) = with_min_span(lo.y, up.y);
+        self.apply_bounds(Vec2::new(lo_x, lo_y), Vec2::new(up_x, up_y));
+        true
+    }
+}
+
+/// Applies the [`Autoscale`] policy of each plot.
+pub(crate) fn apply_autoscale(
+    time: Res<Time>,
+    mut plots: ResMut<PlotMap>,
+    canvas_query: Query<(Entity, &PlotIdComponent, &MeshMaterial2d<CanvasMaterial>)>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+    mut axes_changed_event: EventWriter<AxesChangedEvent>,
+) {
+    let dt = time.delta_secs();
+
+    for plot in plots.plots.values_mut() {
+        // a plot that is not spawned yet starts directly with the right range
+        if plot.entity.is_none() {
+            plot.step_autoscale(f32::INFINITY);
+        }
+    }
+
+    for (canvas_entity, plot_id_comp, material_handle) in canvas_query.iter() {
+        let Some(plot) = plots.get_mut(&plot_id_comp.0) else {
+            continue;
+        };
+
+        if !plot.step_autoscale(dt) {
+            continue;
+        }
+
+        update_plot_labels_event.send(UpdatePlotLabelsEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+        });
+
+        update_target_labels_event.send(UpdateTargetLabelEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+            canvas_material_handle: material_handle.clone(),
+        });
+
+        respawn_all_event.send(RespawnAllEvent {
+            plot_id: plot_id_comp.0,
+        });
+
+        axes_changed_event.send(AxesChangedEvent {
+            plot_id: plot_id_comp.0,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plot_with(points: Vec<Vec2>, autoscale: Autoscale) -> Plot {
+        let mut plot = Plot::default();
+        plot.data.segment_groups.push(SegmentData {
+            data: points,
+            ..Default::default()
+        });
+        plot.autoscale = Autoscale {
+            smoothing: 0.0,
+            ..autoscale
+        };
+        plot
+    }
+
+    fn line(xs: impl Iterator<Item = f32>, y: impl Fn(f32) -> f32) -> Vec<Vec2> {
+        xs.map(|x| Vec2::new(x, y(x))).collect()
+    }
+
+    #[test]
+    fn follow_shows_the_last_points() {
+        let plot = plot_with(line((0..=10).map(|x| x as f32), |x| x * 10.0), Autoscale::follow(4.0));
+
+        let target = plot.autoscale_target().unwrap();
+        assert_eq!((target.lo.x, target.up.x), (6.0, 10.2));
+        // only the visible points, from 60 to 100, are fitted, with 5% margins
+        assert_eq!((target.lo.y, target.up.y), (58.0, 102.0));
+    }
+
+    #[test]
+    fn fit_all_covers_the_whole_data() {
+        let plot = plot_with(line((0..=10).map(|x| x as f32), |x| x * 10.0), Autoscale::fit_all());
+
+        let target = plot.autoscale_target().unwrap();
+        assert_eq!(target.lo, Vec2::new(-0.5, -5.0));
+        assert_eq!(target.up, Vec2::new(10.5, 105.0));
+    }
+
+    #[test]
+    fn narrow_follow_window_far_from_zero_keeps_a_span() {
+        let mut plot = plot_with(line((0..10).map(|k| 1e5 + k as f32), |_| 1.0), Autoscale::follow(1e-3));
+
+        assert!(plot.step_autoscale(0.1));
+        assert!(plot.bounds.lo.x < plot.bounds.up.x);
+        assert!(plot.bounds.lo.y < plot.bounds.up.y);
+    }
+
+    #[test]
+    fn flat_data_far_from_zero_keeps_a_span() {
+        let mut plot = plot_with(line((0..10).map(|x| x as f32), |_| 1e7), Autoscale::follow(5.0));
+
+        assert!(plot.step_autoscale(0.1));
+        assert!(plot.bounds.lo.y < 1e7 && plot.bounds.up.y > 1e7, "{:?}", plot.bounds);
+    }
+
+    #[test]
+    fn smoothing_moves_part_of_the_way() {
+        let mut plot = plot_with(line((0..=10).map(|x| x as f32), |x| x), Autoscale::fit_all());
+        plot.autoscale.smoothing = 1.0;
+        plot.apply_bounds(Vec2::new(-0.5, -20.0), Vec2::new(10.5, 20.0));
+
+        assert!(plot.step_autoscale(0.1));
+        assert!(plot.bounds.lo.y > -20.0 && plot.bounds.lo.y < -0.5, "{:?}", plot.bounds);
+    }
+
+    #[test]
+    fn paused_autoscale_leaves_the_bounds() {
+        let mut plot = plot_with(line((0..=10).map(|x| x as f32), |x| x), Autoscale::fit_all());
+        plot.autoscale.paused = true;
+        let bounds = plot.bounds.clone();
+
+        assert!(!plot.step_autoscale(0.1));
+        assert_eq!(plot.bounds, bounds);
+    }
+}
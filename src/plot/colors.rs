@@ -147,3 +147,38 @@ pub fn make_color_palette() -> HashMap<PlotColor, Vec<Color>> {
 
     colors
 }
+
+/// The first shade of `n` distinct palette colors, cycling through the palette if `n` is large.
+/// Used to give each layer or slice of a chart its own color.
+pub fn palette_colors(n: usize) -> Vec<Color> {
+    let palette = make_color_palette();
+    [
+        PlotColor::Blue,
+        PlotColor::Salmon,
+        PlotColor::Green,
+        PlotColor::Orange,
+        PlotColor::Violet,
+        PlotColor::Yellow,
+        PlotColor::Pink,
+        PlotColor::Latte,
+    ]
+    .iter()
+    .cycle()
+    .take(n)
+    .map(|plot_color| palette[plot_color][0])
+    .collect()
+}
+
+/// Perceptually uniform color map going from dark violet (`t = 0`) to yellow (`t = 1`),
+/// approximating matplotlib's viridis. Used for filled contours and for coloring by magnitude.
+pub fn viridis(t: f32) -> Color {
+    let stops = ["440154", "482878", "3e4989", "31688e", "26828e", "1f9e89", "35b779", "6ece58", "b5de2b", "fde725"]
+        .iter()
+        .map(|h| Srgba::hex(h).unwrap())
+        .collect::<Vec<Srgba>>();
+
+    let x = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let k = (x.floor() as usize).min(stops.len() - 2);
+
+    Color::Srgba(stops[k].mix(&stops[k + 1], x - k as f32))
+}
@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+
+/// Mouse buttons and keys used to interact with the plots. Insert the resource, before or after adding
+/// the [`PlotPlugin`](super::PlotPlugin), to change the bindings, for instance if they conflict with the
+/// controls of a game. The plugin only inserts the default bindings when the resource is missing.
+///
+/// Each action has its own chord by default:
+/// * dragging with the `primary_button` pans the axes, with Ctrl it moves the canvas and with Shift it
+///   selects a region to zoom onto,
+/// * the keyboard shortcuts (targets, tick period, view history and axis restrictions) only act while Alt
+///   is held, so that letters such as T, R, F, A, H, U, X or Y stay available to the application.
+///
+/// Bindings sharing a key make the actions conflict: for instance with Shift as both a box zoom modifier
+/// and a wheel modifier, scrolling while preparing a box zoom would only zoom one axis.
+#[derive(Resource, Debug, Clone)]
+pub struct PlotInputMap {
+    /// Pans the axes by dragging, resizes the canvas by dragging its corners and edges and clicks on data points
+    pub primary_button: MouseButton,
+
+    /// Toggles the target and its coordinates
+    pub target_button: MouseButton,
+
+    /// The keys below, up to the `resume_autoscale_key`, only act while one of these keys is held. Leave it
+    /// empty for the keys to act on their own
+    pub shortcut_modifiers: Vec<KeyCode>,
+
+    /// Pins a target at the cursor. The distance between consecutive pinned targets is measured
+    pub pin_target_key: KeyCode,
+
+    /// Removes the pinned target under the cursor
+    pub unpin_target_key: KeyCode,
+
+    /// Holding this key during a box zoom only zooms the x axis
+    pub box_zoom_x_only: KeyCode,
+
+    /// Holding this key during a box zoom only zooms the y axis
+    pub box_zoom_y_only: KeyCode,
+
+    /// Holding one of these keys while scrolling only zooms the x axis
+    pub zoom_x_only_modifiers: Vec<KeyCode>,
+
+    /// Holding one of these keys while scrolling only zooms the y axis
+    pub zoom_y_only_modifiers: Vec<KeyCode>,
+
+    /// Holding this key while moving the mouse changes the tick period
+    pub tick_period_key: KeyCode,
+
+    /// Goes back to the previous view of the plot under the cursor
+    pub undo_key: KeyCode,
+
+    /// Cancels the last undo on the plot under the cursor
+    pub redo_key: KeyCode,
+
+    /// Restores the initial range of the plot under the cursor
+    pub home_key: KeyCode,
+
+    /// Fits the axes of the plot under the cursor to its data
+    pub fit_key: KeyCode,
+
+    /// Resumes the [`Autoscale`](super::Autoscale) policy of the plot under the cursor, paused by a pan or a zoom
+    pub resume_autoscale_key: KeyCode,
+
+    /// Holding one of these keys while dragging the canvas with the `primary_button` moves it
+    pub move_canvas_modifiers: Vec<KeyCode>,
+
+    /// Holding one of these keys while dragging with the `primary_button` selects a region to zoom onto
+    pub box_zoom_modifiers: Vec<KeyCode>,
+}
+
+impl Default for PlotInputMap {
+    fn default() -> Self {
+        PlotInputMap {
+            primary_button: MouseButton::Left,
+            target_button: MouseButton::Middle,
+            shortcut_modifiers: vec![KeyCode::AltLeft, KeyCode::AltRight],
+            pin_target_key: KeyCode::KeyT,
+            unpin_target_key: KeyCode::Delete,
+            box_zoom_x_only: KeyCode::KeyX,
+            box_zoom_y_only: KeyCode::KeyY,
+            zoom_x_only_modifiers: vec![KeyCode::KeyX],
+            zoom_y_only_modifiers: vec![KeyCode::KeyY],
+            tick_period_key: KeyCode::KeyP,
+            undo_key: KeyCode::KeyU,
+            redo_key: KeyCode::KeyR,
+            home_key: KeyCode::KeyH,
+            fit_key: KeyCode::KeyF,
+            resume_autoscale_key: KeyCode::KeyA,
+            move_canvas_modifiers: vec![KeyCode::ControlLeft, KeyCode::ControlRight],
+            box_zoom_modifiers: vec![KeyCode::ShiftLeft, KeyCode::ShiftRight],
+        }
+    }
+}
+
+impl PlotInputMap {
+    /// True if one of the `shortcut_modifiers` is held, or if there are none.
+    pub(crate) fn shortcuts_enabled(&self, keyboard_input: &ButtonInput<KeyCode>) -> bool {
+        self.shortcut_modifiers.is_empty() || keyboard_input.any_pressed(self.shortcut_modifiers.iter().copied())
+    }
+
+    /// True while `key` is held together with one of the `shortcut_modifiers`.
+    pub(crate) fn shortcut_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, key: KeyCode) -> bool {
+        self.shortcuts_enabled(keyboard_input) && keyboard_input.pressed(key)
+    }
+
+    /// True when `key` is pressed while one of the `shortcut_modifiers` is held.
+    pub(crate) fn shortcut_just_pressed(&self, keyboard_input: &ButtonInput<KeyCode>, key: KeyCode) -> bool {
+        self.shortcuts_enabled(keyboard_input) && keyboard_input.just_pressed(key)
+    }
+
+    /// Axes affected by the mouse wheel, depending on the modifiers being held.
+    pub(crate) fn wheel_zoom_axes(&self, keyboard_input: &ButtonInput<KeyCode>) -> BVec2 {
+        if !self.shortcuts_enabled(keyboard_input) {
+            BVec2::TRUE
+        } else if keyboard_input.any_pressed(self.zoom_x_only_modifiers.iter().copied()) {
+            BVec2::new(true, false)
+        } else if keyboard_input.any_pressed(self.zoom_y_only_modifiers.iter().copied()) {
+            BVec2::new(false, true)
+        } else {
+            BVec2::TRUE
+        }
+    }
+}
+
+/// Marks the camera through which the plots are seen, for the cursor to be mapped to the right world
+/// coordinates. Without it, the first active 2d camera rendering to the window under the cursor is used.
+/// Any orthographic scale and viewport is supported: with split screens, mark the camera of each
+/// viewport showing plots.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct PlotCamera;
+
+/// Interactions enabled on a plot. Everything is enabled by default; use [`PlotInteraction::locked`]
+/// for a plot that only displays data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlotInteraction {
+    /// Moving the axes by dragging
+    pub pan: bool,
+
+    /// Zooming with the mouse wheel
+    pub zoom: bool,
+
+    /// Zooming onto a region selected by dragging (see [`PlotInputMap::box_zoom_modifiers`])
+    pub box_zoom: bool,
+
+    /// Resizing the canvas by dragging its corners and edges. Ignored for the plots of the
+    /// [`PlotLayout`](super::PlotLayout), whose canvases are sized by the layout
+    pub resize: bool,
+
+    /// Moving the canvas by dragging it (see [`PlotInputMap::move_canvas_modifiers`]). Ignored for the plots
+    /// of the [`PlotLayout`](super::PlotLayout), whose canvases are positioned by the layout
+    pub drag: bool,
+
+    /// Toggling and moving the target, and pinning targets with the keyboard
+    pub target: bool,
+
+    /// Changing the tick period with the mouse (see [`PlotInputMap::tick_period_key`])
+    pub tick_period: bool,
+
+    /// Undo, redo, home, fit and resume keys (see [`PlotInputMap::undo_key`])
+    pub view_keys: bool,
+
+    /// Hovering and clicking the data points: tooltips and the [`PlotPointClicked`](super::PlotPointClicked),
+    /// [`PlotPointHoverEnter`](super::PlotPointHoverEnter) and [`PlotPointHoverLeave`](super::PlotPointHoverLeave) events
+    pub points: bool,
+}
+
+impl Default for PlotInteraction {
+    fn default() -> Self {
+        PlotInteraction {
+            pan: true,
+            zoom: true,
+            box_zoom: true,
+            resize: true,
+            drag: true,
+            target: true,
+            tick_period: true,
+            view_keys: true,
+            points: true,
+        }
+    }
+}
+
+impl PlotInteraction {
+    /// No interaction at all.
+    pub fn locked() -> Self {
+        PlotInteraction {
+            pan: false,
+            zoom: false,
+            box_zoom: false,
+            resize: false,
+            drag: false,
+            target: false,
+            tick_period: false,
+            view_keys: false,
+            points: false,
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use super::plot::*;
+
+/// Region of the screen occupied by a [`Figure`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutRegion {
+    /// The whole primary window, assuming a 2d camera centered at the origin
+    Window,
+
+    /// A rectangle in `World` coordinates
+    Rect {
+        /// Center of the rectangle
+        center: Vec2,
+        /// Size of the rectangle in pixels
+        size: Vec2,
+    },
+}
+
+/// Position of a plot inside the grid of a [`Figure`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutCell {
+    pub plot_id: PlotId,
+
+    /// Row of the top left cell occupied by the plot, starting from the top
+    pub row: usize,
+
+    /// Column of the top left cell occupied by the plot, starting from the left
+    pub col: usize,
+
+    /// Number of rows occupied by the plot
+    pub row_span: usize,
+
+    /// Number of columns occupied by the plot
+    pub col_span: usize,
+}
+
+/// Arranges several plots in rows and columns inside a region. The `canvas_position` and
+/// `canvas_size` of each plot are computed by bevy_plot, and recomputed whenever the layout or
+/// the window changes size. Add the figure to the [`PlotLayout`] resource.
+///
+/// ```ignore
+/// let mut figure = Figure::new(2, 2);
+/// figure.add(plots.add(plot1), 0, 0).add(plots.add(plot2), 0, 1).add_span(plots.add(plot3), 1, 0, 1, 2);
+/// layout.add(figure);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Figure {
+    /// Number of rows of the grid
+    pub rows: usize,
+
+    /// Number of columns of the grid
+    pub cols: usize,
+
+    /// Region occupied by the figure. The default is the whole window
+    pub region: LayoutRegion,
+
+    /// Space in pixels between the edges of the region and the outer plots
+    pub margin: Vec2,
+
+    /// Horizontal and vertical space in pixels between neighbouring plots
+    pub spacing: Vec2,
+
+    /// Space in pixels left above each plot for a title
+    pub title_height: f32,
+
+    pub cells: Vec<LayoutCell>,
+}
+
+impl Figure {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Figure {
+            rows: rows.max(1),
+            cols: cols.max(1),
+            region: LayoutRegion::Window,
+            margin: Vec2::splat(10.0),
+            spacing: Vec2::splat(10.0),
+            title_height: 0.0,
+            cells: vec![],
+        }
+    }
+
+    /// Places the plot in the cell at `row` and `col`.
+    pub fn add(&mut self, plot_id: PlotId, row: usize, col: usize) -> &mut Self {
+        self.add_span(plot_id, row, col, 1, 1)
+    }
+
+    /// Places the plot in the cell at `row` and `col`, stretched over `row_span` rows and `col_span` columns.
+    pub fn add_span(&mut self, plot_id: PlotId, row: usize, col: usize, row_span: usize, col_span: usize) -> &mut Self {
+        self.cells.push(LayoutCell {
+            plot_id,
+            row,
+            col,
+            row_span: row_span.max(1),
+            col_span: col_span.max(1),
+        });
+        self
+    }
+
+    /// Center and size of the canvas of each plot in the figure, given the size of the window.
+    pub fn compute(&self, window_size: Vec2) -> Vec<(PlotId, Vec2, Vec2)> {
+        let (center, size) = match &self.region {
+            LayoutRegion::Window => (Vec2::ZERO, window_size),
+            LayoutRegion::Rect { center, size } => (*center, *size),
+        };
+
+        let grid = Vec2::new(self.cols as f32, self.rows as f32);
+        let available = size - self.margin * 2.0 - self.spacing * (grid - 1.0);
+        let cell_size = (available / grid).max(Vec2::ONE);
+
+        // top left corner of the region
+        let top_left = center + Vec2::new(-size.x, size.y) / 2.0 + Vec2::new(self.margin.x, -self.margin.y);
+
+        self.cells
+            .iter()
+            .map(|cell| {
+                let span = Vec2::new(cell.col_span as f32, cell.row_span as f32);
+                let outer_size = cell_size * span + self.spacing * (span - 1.0);
+                let canvas_size = Vec2::new(outer_size.x, (outer_size.y - self.title_height).max(1.0));
+
+                let corner = top_left
+                    + Vec2::new(
+                        cell.col as f32 * (cell_size.x + self.spacing.x),
+                        -(cell.row as f32 * (cell_size.y + self.spacing.y) + self.title_height),
+                    );
+                let canvas_position = corner + Vec2::new(canvas_size.x, -canvas_size.y) / 2.0;
+
+                (cell.plot_id, canvas_position, canvas_size)
+            })
+            .collect()
+    }
+}
+
+/// Corner, edge or center of the window a plot is attached to. See [`AnchoredPlot`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WindowAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl WindowAnchor {
+    /// Direction of the anchor from the center of the window.
+    fn direction(&self) -> Vec2 {
+        match self {
+            WindowAnchor::TopLeft => Vec2::new(-1.0, 1.0),
+            WindowAnchor::Top => Vec2::new(0.0, 1.0),
+            WindowAnchor::TopRight => Vec2::new(1.0, 1.0),
+            WindowAnchor::Left => Vec2::new(-1.0, 0.0),
+            WindowAnchor::Center => Vec2::ZERO,
+            WindowAnchor::Right => Vec2::new(1.0, 0.0),
+            WindowAnchor::BottomLeft => Vec2::new(-1.0, -1.0),
+            WindowAnchor::Bottom => Vec2::new(0.0, -1.0),
+            WindowAnchor::BottomRight => Vec2::new(1.0, -1.0),
+        }
+    }
+}
+
+/// Width or height of an anchored plot.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutLength {
+    /// Number of pixels
+    Px(f32),
+    /// Percentage of the width or height of the window
+    Percent(f32),
+}
+
+impl LayoutLength {
+    fn resolve(&self, window_length: f32) -> f32 {
+        match self {
+            LayoutLength::Px(px) => *px,
+            LayoutLength::Percent(percent) => window_length * percent / 100.0,
+        }
+        .max(1.0)
+    }
+}
+
+/// Plot attached to a corner or an edge of the window, for instance a HUD graph that must stay in the top
+/// right corner whatever the size of the window. Add it to the [`PlotLayout`] resource.
+///
+/// ```ignore
+/// layout.anchor(AnchoredPlot::new(plot_id, WindowAnchor::TopRight, LayoutLength::Percent(30.0), LayoutLength::Px(200.0)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchoredPlot {
+    pub plot_id: PlotId,
+    pub anchor: WindowAnchor,
+    pub width: LayoutLength,
+    pub height: LayoutLength,
+
+    /// Space in pixels between the canvas and the edges of the window it is anchored to
+    pub margin: Vec2,
+}
+
+impl AnchoredPlot {
+    pub fn new(plot_id: PlotId, anchor: WindowAnchor, width: LayoutLength, height: LayoutLength) -> Self {
+        AnchoredPlot {
+            plot_id,
+            anchor,
+            width,
+            height,
+            margin: Vec2::splat(10.0),
+        }
+    }
+
+    /// Center and size of the canvas, given the size of the window.
+    pub fn compute(&self, window_size: Vec2) -> (Vec2, Vec2) {
+        let canvas_size = Vec2::new(self.width.resolve(window_size.x), self.height.resolve(window_size.y));
+        let canvas_position = self.anchor.direction() * (window_size / 2.0 - self.margin - canvas_size / 2.0);
+
+        (canvas_position, canvas_size)
+    }
+}
+
+/// Plots positioned automatically, either in the grid of a [`Figure`] or anchored to the window
+/// (see [`AnchoredPlot`]). The positions are computed again when this resource changes or when the
+/// window is resized, assuming a 2d camera centered at the origin.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PlotLayout {
+    pub figures: Vec<Figure>,
+    pub anchored: Vec<AnchoredPlot>,
+}
+
+impl PlotLayout {
+    pub fn add(&mut self, figure: Figure) {
+        self.figures.push(figure);
+    }
+
+    pub fn anchor(&mut self, anchored_plot: AnchoredPlot) {
+        self.anchored.push(anchored_plot);
+    }
+
+    /// True if the canvas of the plot is positioned by the layout, either in a figure or anchored to the window.
+    pub fn manages(&self, plot_id: PlotId) -> bool {
+        self.figures
+            .iter()
+            .any(|figure| figure.cells.iter().any(|cell| cell.plot_id == plot_id))
+            || self.anchored.iter().any(|anchored| anchored.plot_id == plot_id)
+    }
+}
+
+/// Moves and resizes the canvases of the plots belonging to a figure or anchored to the window. Plots that
+/// have already been spawned are despawned and spawned again with their new position and size.
+pub(crate) fn layout_plots(
+    mut commands: Commands,
+    layout: Res<PlotLayout>,
+    mut plots: ResMut<PlotMap>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let window_size = window.size();
+
+    let figure_cells = layout.figures.iter().flat_map(|figure| figure.compute(window_size));
+    let anchored_plots = layout.anchored.iter().map(|anchored| {
+        let (canvas_position, canvas_size) = anchored.compute(window_size);
+        (anchored.plot_id, canvas_position, canvas_size)
+    });
+
+    for (plot_id, canvas_position, canvas_size) in figure_cells.chain(anchored_plots) {
+        let Some(plot) = plots.get_mut(&plot_id) else {
+            continue;
+        };
+
+        // nothing to do if the plot is already in place (up to a fraction of a pixel)
+        if plot.canvas_position.distance(canvas_position) < 0.5 && plot.canvas_size.distance(canvas_size) < 0.5 {
+            continue;
+        }
+
+        plot.canvas_position = canvas_position;
+        plot.canvas_size = canvas_size;
+
+        if let Some(entity) = plot.entity.take() {
+            commands.entity(entity).despawn_recursive();
+            plot.do_spawn_plot = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn figure_splits_the_window_into_equal_cells() {
+        let mut figure = Figure::new(2, 2);
+        figure.add(1, 0, 0).add(2, 1, 1);
+
+        // 420 pixels minus two margins and one spacing of 10 pixels leave 195 pixels per cell
+        let cells = figure.compute(Vec2::splat(420.0));
+        assert_eq!(
+            cells,
+            vec![
+                (1, Vec2::new(-102.5, 102.5), Vec2::splat(195.0)),
+                (2, Vec2::new(102.5, -102.5), Vec2::splat(195.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn spanned_cell_covers_the_spacing_between_its_cells() {
+        let mut figure = Figure::new(2, 2);
+        figure.add_span(1, 1, 0, 1, 2);
+
+        let cells = figure.compute(Vec2::splat(420.0));
+        assert_eq!(cells, vec![(1, Vec2::new(0.0, -102.5), Vec2::new(400.0, 195.0))]);
+    }
+
+    #[test]
+    fn title_height_is_taken_from_the_top_of_the_canvas() {
+        let mut figure = Figure::new(2, 2);
+        figure.title_height = 20.0;
+        figure.add(1, 0, 0);
+
+        let cells = figure.compute(Vec2::splat(420.0));
+        assert_eq!(cells, vec![(1, Vec2::new(-102.5, 92.5), Vec2::new(195.0, 175.0))]);
+    }
+
+    #[test]
+    fn figure_fills_its_rect_region() {
+        let mut figure = Figure::new(1, 1);
+        figure.region = LayoutRegion::Rect {
+            center: Vec2::new(100.0, 0.0),
+            size: Vec2::new(220.0, 120.0),
+        };
+        figure.add(1, 0, 0);
+
+        // the window size is not used
+        let cells = figure.compute(Vec2::splat(1000.0));
+        assert_eq!(cells, vec![(1, Vec2::new(100.0, 0.0), Vec2::new(200.0, 100.0))]);
+    }
+
+    #[test]
+    fn anchored_plot_stays_in_its_corner() {
+        let anchored = AnchoredPlot::new(1, WindowAnchor::TopRight, LayoutLength::Px(200.0), LayoutLength::Px(100.0));
+
+        // 10 pixels from the top and right edges of an 800x600 window
+        let (position, size) = anchored.compute(Vec2::new(800.0, 600.0));
+        assert_eq!((position, size), (Vec2::new(290.0, 240.0), Vec2::new(200.0, 100.0)));
+    }
+
+    #[test]
+    fn anchored_plot_in_percent_follows_the_window() {
+        let anchored = AnchoredPlot::new(1, WindowAnchor::Bottom, LayoutLength::Percent(50.0), LayoutLength::Percent(25.0));
+
+        let (position, size) = anchored.compute(Vec2::new(800.0, 600.0));
+        assert_eq!((position, size), (Vec2::new(0.0, -215.0), Vec2::new(400.0, 150.0)));
+    }
+
+    #[test]
+    fn centered_anchored_plot_ignores_the_margin() {
+        let anchored = AnchoredPlot::new(1, WindowAnchor::Center, LayoutLength::Px(200.0), LayoutLength::Percent(0.0));
+
+        // a length is at least one pixel
+        let (position, size) = anchored.compute(Vec2::new(800.0, 600.0));
+        assert_eq!((position, size), (Vec2::ZERO, Vec2::new(200.0, 1.0)));
+    }
+}
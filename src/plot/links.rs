@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use std::collections::HashSet;
+
+use super::plot::*;
+use crate::canvas::*;
+
+/// Axes shared by the plots of a [`LinkGroup`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinkedAxes {
+    X,
+    Y,
+    XY,
+}
+
+impl LinkedAxes {
+    fn x(&self) -> bool {
+        matches!(self, LinkedAxes::X | LinkedAxes::XY)
+    }
+
+    fn y(&self) -> bool {
+        matches!(self, LinkedAxes::Y | LinkedAxes::XY)
+    }
+}
+
+/// Plots whose ranges follow each other: panning or zooming one of them with the mouse applies the
+/// same range to the linked axes of the others, including the secondary y axes when the y axes are linked
+/// and both plots have one. The [`Autoscale`](super::Autoscale) policy of the other plots is paused.
+#[derive(Debug, Clone)]
+pub struct LinkGroup {
+    pub plots: Vec<PlotId>,
+    pub axes: LinkedAxes,
+}
+
+/// Groups of plots with linked axes. For example, to scroll three timelines in lockstep:
+///
+/// ```ignore
+/// links.link(vec![cpu_id, gpu_id, network_id], LinkedAxes::X);
+/// ```
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PlotLinks {
+    pub groups: Vec<LinkGroup>,
+}
+
+impl PlotLinks {
+    pub fn link(&mut self, plots: Vec<PlotId>, axes: LinkedAxes) {
+        self.groups.push(LinkGroup { plots, axes });
+    }
+}
+
+/// Copies the range of the plots that have just been panned or zoomed to the plots linked to them.
+pub(crate) fn sync_linked_axes(
+    links: Res<PlotLinks>,
+    mut plots: ResMut<PlotMap>,
+    mut axes_changed_event: EventReader<AxesChangedEvent>,
+    canvas_query: Query<(Entity, &PlotIdComponent, &MeshMaterial2d<CanvasMaterial>)>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+) {
+    let changed_ids = axes_changed_event
+        .read()
+        .map(|event| event.plot_id)
+        .collect::<HashSet<PlotId>>();
+
+    let mut updated_ids = HashSet::new();
+
+    for plot_id in changed_ids.iter() {
+        let Some(source) = plots.get(plot_id).cloned() else {
+            continue;
+        };
+
+        for group in links.groups.iter().filter(|group| group.plots.contains(plot_id)) {
+            for linked_id in group.plots.iter().filter(|id| *id != plot_id) {
+                let Some(plot) = plots.get_mut(linked_id) else {
+                    continue;
+                };
+
+                if group.axes.x() {
+                    plot.bounds.lo.x = source.bounds.lo.x;
+                    plot.bounds.up.x = source.bounds.up.x;
+                    plot.tick_period.x = source.tick_period.x;
+                }
+
+                if group.axes.y() {
+                    plot.bounds.lo.y = source.bounds.lo.y;
+                    plot.bounds.up.y = source.bounds.up.y;
+                    plot.tick_period.y = source.tick_period.y;
+
+                    let axes = (plot.secondary_axis.as_mut(), source.secondary_axis.as_ref());
+                    if let (Some(axis), Some(source_axis)) = axes {
+                        axis.lo = source_axis.lo;
+                        axis.up = source_axis.up;
+                        axis.tick_period = source_axis.tick_period;
+                    }
+                }
+
+                if group.axes == LinkedAxes::XY {
+                    plot.zoom = source.zoom;
+                }
+
+                // like a pan or a zoom, the link takes over the autoscale policy of the plot
+                plot.autoscale.paused = true;
+
+                plot.compute_zeros();
+                updated_ids.insert(*linked_id);
+            }
+        }
+    }
+
+    for (canvas_entity, plot_id_comp, material_handle) in canvas_query.iter() {
+        if !updated_ids.contains(&plot_id_comp.0) {
+            continue;
+        }
+
+        update_plot_labels_event.send(UpdatePlotLabelsEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+        });
+
+        update_target_labels_event.send(UpdateTargetLabelEvent {
+            plot_id: plot_id_comp.0,
+            canvas_entity,
+            canvas_material_handle: material_handle.clone(),
+        });
+
+        respawn_all_event.send(RespawnAllEvent {
+            plot_id: plot_id_comp.0,
+        });
+    }
+}
@@ -1,7 +1,19 @@
+pub mod autoscale;
 mod colors;
+pub mod interaction;
+pub mod layout;
+pub mod links;
 pub mod plot;
 pub mod plot_format;
+pub mod texture;
+pub mod view;
 
+pub use autoscale::*;
 pub use colors::*;
+pub use interaction::*;
+pub use layout::*;
+pub use links::*;
 pub use plot::*;
 pub use plot_format::*;
+pub use texture::*;
+pub use view::*;
@@ -4,14 +4,37 @@ use bevy::{
     // asset::Assets,
     prelude::*,
     sprite::Material2dPlugin,
+    render::view::VisibilitySystems,
+    window::WindowResized,
     // render::render_resource::AsBindGroup,
 };
 
-use super::colors::ColorPalette;
+use std::sync::Arc;
+
+use super::autoscale::*;
+use super::colors::*;
+use super::interaction::*;
+use super::layout::*;
+use super::links::*;
 use super::plot_format::*;
+use super::texture::*;
+use super::view::*;
 
+use crate::annotation::*;
+use crate::candlestick::*;
 use crate::canvas::*;
+use crate::contour::*;
 use crate::inputs::*;
+use crate::line::*;
+use crate::pie::*;
+use crate::quiver::*;
+use crate::radar::*;
+use crate::shapes::*;
+use crate::stacked_area::*;
+use crate::stem::*;
+use crate::tooltip::*;
+#[cfg(feature = "ui")]
+use crate::ui::*;
 use crate::util::*;
 
 use std::collections::HashMap;
@@ -27,23 +50,43 @@ pub struct PlotPlugin;
 //
 // canvas: 0.0001
 // text and labels: 1.0001
+// pie slices: 1.01
+// pie labels: 1.015
+// radar: 1.01
+// radar labels and legend: 1.015
+// stacked areas: 1.02
+// annotation spans and rectangles: 1.03
+// contour fill: 1.04
+// contour lines: 1.05
+// contour labels: 1.06
+// candlesticks: 1.07
+// stems: 1.075
+// quiver: 1.08
 // bezier 1.10
 // segments: 1.11
 // markers: 1.12
+// annotation lines and arrows: 1.15
+// annotation text: 1.16
+// pinned targets and measurement lines: 1.19
 // target text: 1.2
+// box zoom selection: 1.22
+// hovered point highlight: 1.25
+// tooltip background: 1.3
+// tooltip text: 1.31
 
 // TODO:
 // 1) Area under the curve
 // 2) Automatically color curve, segments and markers with palette
 // 3) Global variable for z planes
 // 4) optimize
-// 5) add 3d mesh materials
 
 impl Plugin for PlotPlugin {
     fn build(&self, app: &mut App) {
         app
             // canvas
             .add_plugins(Material2dPlugin::<CanvasMaterial>::default())
+            .add_plugins(ShapeMesh2dPlugin)
+            .add_plugins(PieMesh2dPlugin)
             // .add_plugin(MarkerMesh2dPlugin)
             // .add_plugin(BezierMesh2dPlugin)
             // .add_plugin(SegmentMesh2dPlugin)
@@ -54,6 +97,11 @@ impl Plugin for PlotPlugin {
             .add_event::<RespawnAllEvent>()
             .add_event::<WaitForUpdatePlotLabelsEvent>()
             .add_event::<UpdateTargetLabelEvent>()
+            .add_event::<AxesChangedEvent>()
+            .add_event::<PlotPointClicked>()
+            .add_event::<PlotPointHoverEnter>()
+            .add_event::<PlotPointHoverLeave>()
+            .add_event::<PlotViewEvent>()
             // .add_event::<UpdateBezierShaderEvent>()
             // .add_event::<SpawnBezierCurveEvent>()
             // .add_asset::<Plot>()
@@ -61,6 +109,11 @@ impl Plugin for PlotPlugin {
             .insert_resource(Cursor::default())
             .insert_resource(TickLabelFont { maybe_font: None })
             .insert_resource(PlotMap::default())
+            .insert_resource(PlotLayout::default())
+            .insert_resource(PlotLinks::default())
+            .init_resource::<PlotInputMap>()
+            .insert_resource(PointIndex::default())
+            .insert_resource(HoveredPoints::default())
             // .insert_resource(Time::<Fixed>::from_hz(240.0))
             .add_systems(
                 Update,
@@ -86,15 +139,85 @@ impl Plugin for PlotPlugin {
                     release_all,
                     do_spawn_plot,
                     update_target,
-                    update_mouse_target,
+                    // the targets go to the canvas picked by change_plot
+                    update_mouse_target.after(change_plot),
+                    update_pinned_targets.after(change_plot),
                     spawn_graph,
                     adjust_graph_size,
+                    drag_canvas,
                     update_plot_labels,
                     record_mouse_events_system,
+                    update_box_zoom,
                     // animate_bezier,
                 ),
+            )
+            .add_systems(
+                Update,
+                layout_plots
+                    .run_if(resource_changed::<PlotLayout>.or(on_event::<WindowResized>))
+                    .before(do_spawn_plot),
+            )
+            .add_systems(
+                Update,
+                (view_keys, apply_view_actions, apply_autoscale)
+                    .chain()
+                    .after(change_plot)
+                    .before(do_spawn_plot),
+            )
+            .add_systems(
+                Update,
+                sync_linked_axes
+                    .after(adjust_graph_axes)
+                    .before(wait_for_graph_spawn),
+            )
+            .add_systems(
+                Update,
+                (
+                    contour_setup,
+                    quiver_setup,
+                    candlestick_setup,
+                    line_setup,
+                    scatter_setup,
+                    stem_setup,
+                    stacked_area_setup,
+                    pie_setup,
+                    radar_setup,
+                    annotation_setup,
+                    build_point_index,
+                )
+                    .after(spawn_graph),
+            )
+            .add_systems(
+                Update,
+                (update_hovered_points, update_tooltips)
+                    .chain()
+                    .after(build_point_index)
+                    .after(change_plot),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    update_texture_cameras.before(TransformSystem::TransformPropagate),
+                    propagate_texture_layers.before(VisibilitySystems::CheckVisibility),
+                ),
             );
 
+        #[cfg(feature = "ui")]
+        app.add_systems(
+            Update,
+            (
+                layout_plot_nodes.before(do_spawn_plot),
+                // the cursor is moved onto the offscreen canvas before any system reads it
+                plot_node_cursor
+                    .after(record_mouse_events_system)
+                    .before(change_plot)
+                    .before(adjust_graph_axes)
+                    .before(adjust_graph_size)
+                    .before(drag_canvas)
+                    .before(update_box_zoom),
+            ),
+        );
+
         // .add_systems(
         //     Update,
         //     (
@@ -153,6 +276,11 @@ fn do_spawn_plot(
         if plot.do_spawn_plot {
             let canvas = plot.make_canvas();
 
+            // the range at the first spawn is the one restored by the home action
+            if plot.view_history.home.is_none() {
+                plot.view_history.home = Some(plot.view_state());
+            }
+
             spawn_plot_event.send(SpawnGraphEvent {
                 canvas,
                 plot_id: plot.id,
@@ -194,6 +322,25 @@ pub(crate) struct WaitForUpdatePlotLabelsEvent {
     pub quad_entity: Entity,
 }
 
+/// Tick period close to a tenth of `range`, of the form 1, 2 or 5 times a power of ten.
+pub(crate) fn nice_tick_period(range: f32) -> f32 {
+    let exact_tick = range / 10.0;
+
+    // find order of magnitude of the exact tick
+    let order = exact_tick.log10().floor();
+    let mag = 10_f32.powf(order);
+
+    [mag * 1.0, mag * 2.0, mag * 5.0]
+        .into_iter()
+        .min_by(|a, b| {
+            (a - exact_tick)
+                .abs()
+                .partial_cmp(&(b - exact_tick).abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap()
+}
+
 /// Component that serves as identification for the nth curve group of the `bezier_groups` field
 /// of [`PlotData`].
 #[derive(Component)]
@@ -208,12 +355,48 @@ pub struct BezierCurveNumber(pub usize);
 // }
 
 // #[derive(Debug, Clone, AsBindGroup)]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct PlotCanvasBounds {
     pub up: Vec2,
     pub lo: Vec2,
 }
 
+/// Vertical axis a series is bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum YAxis {
+    /// The main y axis, with the tick labels on the left of the canvas
+    #[default]
+    Left,
+    /// The secondary y axis (see [`Plot::set_secondary_bounds`]), with the tick labels on the right of the canvas
+    Right,
+}
+
+/// Right-hand y axis, with its own range, ticks and labels. The grid of the canvas follows the main y axis only.
+#[derive(Debug, Clone)]
+pub struct SecondaryAxis {
+    /// Lower bound of the axis
+    pub lo: f32,
+
+    /// Upper bound of the axis
+    pub up: f32,
+
+    /// Distance between consecutive tick labels
+    pub tick_period: f32,
+
+    /// Formats the tick labels. If None, the labels are formatted like the ones of the main axes
+    pub formatter: Option<fn(f32) -> String>,
+
+    /// Color of the tick labels
+    pub label_color: Color,
+}
+
+impl SecondaryAxis {
+    /// Converts a value on this axis to the corresponding value on the main y axis.
+    pub(crate) fn to_primary(&self, y: f32, bounds: &PlotCanvasBounds) -> f32 {
+        bounds.lo.y + (y - self.lo) / (self.up - self.lo) * (bounds.up.y - bounds.lo.y)
+    }
+}
+
 // impl From<PlotCanvasBounds> for PlotCanvasBoundsCrevice {
 //     fn from(bounds: PlotCanvasBounds) -> Self {
 //         Self {
@@ -284,6 +467,12 @@ pub struct MarkerData {
 
     /// If true, the markers are displayed with a black border
     pub draw_contour: bool,
+
+    /// The y axis against which the data is drawn
+    pub y_axis: YAxis,
+
+    /// Name of the series, shown in the hover tooltips
+    pub name: Option<String>,
 }
 
 impl Default for MarkerData {
@@ -295,6 +484,8 @@ impl Default for MarkerData {
             marker_style: MarkerStyle::Circle,
             size: 1.0,
             draw_contour: false,
+            y_axis: YAxis::Left,
+            name: None,
         }
     }
 }
@@ -313,6 +504,12 @@ pub struct SegmentData {
     pub line_style: LineStyle,
     pub draw_contour: bool,
     pub mech: bool,
+    /// If set, consecutive points are joined by a horizontal and a vertical segment (staircase)
+    pub step: Option<StepMode>,
+    /// The y axis against which the data is drawn
+    pub y_axis: YAxis,
+    /// Name of the series, shown in the hover tooltips
+    pub name: Option<String>,
 }
 
 impl Default for SegmentData {
@@ -325,6 +522,519 @@ impl Default for SegmentData {
             line_style: LineStyle::Solid,
             draw_contour: false,
             mech: false,
+            step: None,
+            y_axis: YAxis::Left,
+            name: None,
+        }
+    }
+}
+
+impl SegmentData {
+    /// Vertices of the polyline to be drawn: the data itself, or the corners of
+    /// the staircase if `step` is set.
+    pub fn points(&self) -> Vec<Vec2> {
+        let step = match &self.step {
+            Some(step) => step,
+            None => return self.data.clone(),
+        };
+
+        let mut points = Vec::with_capacity(self.data.len() * 3);
+
+        for pair in self.data.windows(2) {
+            let (p0, p1) = (pair[0], pair[1]);
+            points.push(p0);
+            match step {
+                StepMode::Pre => points.push(Vec2::new(p0.x, p1.y)),
+                StepMode::Post => points.push(Vec2::new(p1.x, p0.y)),
+                StepMode::Mid => {
+                    let x_mid = (p0.x + p1.x) / 2.0;
+                    points.push(Vec2::new(x_mid, p0.y));
+                    points.push(Vec2::new(x_mid, p1.y));
+                }
+            }
+        }
+        points.extend(self.data.last());
+
+        points
+    }
+}
+
+/// Where the vertical jump of a step plot happens between two consecutive points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepMode {
+    /// The value changes at the start of the interval: the jump is at the previous point
+    Pre,
+    /// The jump is halfway between the two points
+    Mid,
+    /// The value holds until the next point: the jump is at the next point
+    Post,
+}
+
+/// Struct containing the data to be plotted and metaparameters of a stem plot.
+/// It can be found in the `data.stem_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct StemData {
+    /// The data to be displayed in the stem plot
+    pub data: Vec<Vec2>,
+
+    /// The stems go from `y = baseline` to each data point
+    pub baseline: f32,
+
+    /// Color of the stems
+    pub color: Color,
+
+    /// Thickness of the stems
+    pub size: f32,
+
+    /// Marker on top of each stem. Only its `color`, `marker_style` and `size` fields are used.
+    pub marker: MarkerData,
+
+    /// The y axis against which the data is drawn
+    pub y_axis: YAxis,
+
+    /// Name of the series, shown in the hover tooltips
+    pub name: Option<String>,
+}
+
+impl Default for StemData {
+    fn default() -> Self {
+        StemData {
+            data: vec![],
+            baseline: 0.0,
+            color: Color::Srgba(Srgba::hex("8eb274").unwrap()),
+            size: 1.0,
+            marker: MarkerData {
+                color: Color::Srgba(Srgba::hex("8eb274").unwrap()),
+                ..Default::default()
+            },
+            y_axis: YAxis::Left,
+            name: None,
+        }
+    }
+}
+
+/// Scalar field of which the iso-lines are drawn by [`Plot::contour`].
+#[derive(Debug, Clone)]
+pub enum ContourField {
+    /// Explicit function of `x` and `y`. It is sampled over the current bounds of the plot
+    /// every time the axes are moved or zoomed.
+    Function(fn(f32, f32) -> f32),
+
+    /// Values sampled on a regular grid going from `lo` to `up`, where `values[j][i]` is the value
+    /// on the `j`-th row (y axis) and `i`-th column (x axis).
+    Grid { lo: Vec2, up: Vec2, values: Vec<Vec<f32>> },
+}
+
+/// Struct containing the data to be plotted and metaparameters of a contour plot.
+/// It can be found in the `data.contour_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct ContourData {
+    /// The scalar field to be displayed
+    pub field: ContourField,
+
+    /// Values of the field at which iso-lines are drawn, in increasing order
+    pub levels: Vec<f32>,
+
+    /// Color of the iso-lines
+    pub color: Color,
+
+    /// Thickness of the iso-lines. If the `line_style` is set to `LineStyle::None`, the
+    /// iso-lines are not drawn
+    pub size: f32,
+
+    /// Solid, dashed or hidden iso-lines
+    pub line_style: LineStyle,
+
+    /// If true, the bands between consecutive levels are filled with a color map
+    pub fill: bool,
+
+    /// If true, the value of each level is written along its iso-line
+    pub show_labels: bool,
+
+    /// Number of cells per axis used to sample a [`ContourField::Function`]
+    pub num_points: usize,
+}
+
+impl Default for ContourData {
+    fn default() -> Self {
+        ContourData {
+            field: ContourField::Function(|x: f32, y: f32| x * y),
+            levels: vec![],
+            color: Color::srgb(0.9, 0.9, 0.9),
+            size: 1.0,
+            line_style: LineStyle::Solid,
+            fill: false,
+            show_labels: false,
+            num_points: 64,
+        }
+    }
+}
+
+/// Vectors drawn as arrows by [`Plot::quiver`] and [`Plot::quiver_func`].
+#[derive(Debug, Clone)]
+pub enum QuiverField {
+    /// Pairs of (position, vector), in plot coordinates.
+    Data(Vec<(Vec2, Vec2)>),
+
+    /// Explicit function of `x` and `y` returning a vector. It is sampled on a regular grid
+    /// over the current bounds of the plot every time the axes are moved or zoomed.
+    Function(fn(f32, f32) -> Vec2),
+}
+
+/// Struct containing the data to be plotted and metaparameters of a vector field (or quiver) plot.
+/// It can be found in the `data.quiver_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct QuiverData {
+    /// The vectors to be displayed
+    pub field: QuiverField,
+
+    /// Color of the arrows, unless `color_by_magnitude` is true
+    pub color: Color,
+
+    /// Thickness of the arrows
+    pub size: f32,
+
+    /// Multiplies the vectors to obtain the length of the arrows in plot coordinates. If None, the
+    /// vectors are scaled so that the longest arrow on screen spans the distance in pixels between two
+    /// grid samples along the tighter axis (or a tenth of the canvas for a [`QuiverField::Data`])
+    pub scale: Option<f32>,
+
+    /// If true, the arrows are colored from dark violet (shortest) to yellow (longest)
+    pub color_by_magnitude: bool,
+
+    /// Number of samples per axis used to sample a [`QuiverField::Function`]
+    pub num_points: usize,
+}
+
+impl Default for QuiverData {
+    fn default() -> Self {
+        QuiverData {
+            field: QuiverField::Data(vec![]),
+            color: Color::srgb(0.9, 0.9, 0.9),
+            size: 1.0,
+            scale: None,
+            color_by_magnitude: false,
+            num_points: 20,
+        }
+    }
+}
+
+/// Struct containing the data to be plotted and metaparameters of a candlestick (or OHLC) plot.
+/// It can be found in the `data.candlestick_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct CandlestickData {
+    /// The periods to be displayed, sorted by time
+    pub data: Vec<Candle>,
+
+    /// Color of the candles closing higher than they opened
+    pub up_color: Color,
+
+    /// Color of the candles closing lower than they opened
+    pub down_color: Color,
+
+    /// Width of the candle bodies relative to the shortest time between two candles
+    pub size: f32,
+
+    /// If true, the volumes are shown as bars in a strip at the bottom of the canvas
+    pub show_volume: bool,
+
+    /// Height of the volume strip relative to the height of the canvas
+    pub volume_height: f32,
+
+    /// The y axis against which the prices are drawn
+    pub y_axis: YAxis,
+
+    /// Name of the series, shown in the hover tooltips
+    pub name: Option<String>,
+}
+
+impl Default for CandlestickData {
+    fn default() -> Self {
+        CandlestickData {
+            data: vec![],
+            up_color: Color::Srgba(Srgba::hex("8eb274").unwrap()),
+            down_color: Color::Srgba(Srgba::hex("e6564d").unwrap()),
+            size: 0.7,
+            show_volume: false,
+            volume_height: 0.2,
+            y_axis: YAxis::Left,
+            name: None,
+        }
+    }
+}
+
+/// Struct containing the data to be plotted and metaparameters of a stacked area chart.
+/// It can be found in the `data.stacked_area_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct StackedAreaData {
+    /// Common x values of all the layers
+    pub xs: Vec<f32>,
+
+    /// The y values of each layer, stacked on top of the previous ones. Negative values are drawn as
+    /// zero, since a layer cannot go below the one under it
+    pub layers: Vec<Vec<f32>>,
+
+    /// Fill color of each layer. Layers without a color are drawn in gray
+    pub colors: Vec<Color>,
+
+    /// If true, the layers are rescaled so that they sum up to 100 at every x value
+    pub normalize: bool,
+
+    /// Thickness of the line drawn on top of each layer. If the `line_style` is set to
+    /// `LineStyle::None`, the lines are not drawn
+    pub size: f32,
+    pub line_style: LineStyle,
+
+    /// The y axis against which the layers are drawn
+    pub y_axis: YAxis,
+}
+
+impl Default for StackedAreaData {
+    fn default() -> Self {
+        StackedAreaData {
+            xs: vec![],
+            layers: vec![],
+            colors: vec![],
+            normalize: false,
+            size: 1.0,
+            line_style: LineStyle::Solid,
+            y_axis: YAxis::Left,
+        }
+    }
+}
+
+impl StackedAreaData {
+    /// Upper boundary of each layer: the cumulative sum of the layers at every x value,
+    /// in percent if `normalize` is true. Negative (and NaN) values count as zero.
+    pub fn stacked(&self) -> Vec<Vec<f32>> {
+        let num_points = self
+            .layers
+            .iter()
+            .map(|layer| layer.len())
+            .fold(self.xs.len(), usize::min);
+
+        let mut cumulative = vec![0.0; num_points];
+        let mut stacked = self
+            .layers
+            .iter()
+            .map(|layer| {
+                for (sum, y) in cumulative.iter_mut().zip(layer.iter()) {
+                    *sum += y.max(0.0);
+                }
+                cumulative.clone()
+            })
+            .collect::<Vec<Vec<f32>>>();
+
+        if self.normalize {
+            for layer in stacked.iter_mut() {
+                for (y, total) in layer.iter_mut().zip(cumulative.iter()) {
+                    if *total != 0.0 {
+                        *y *= 100.0 / total;
+                    }
+                }
+            }
+        }
+
+        stacked
+    }
+}
+
+/// Struct containing the data to be plotted and metaparameters of a pie (or donut) chart.
+/// It can be found in the `data.pie_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct PieData {
+    /// Size of each slice. Negative values are treated as zero
+    pub values: Vec<f32>,
+
+    /// Name of each slice, written next to it
+    pub labels: Vec<String>,
+
+    /// Color of each slice. Slices without a color are drawn in gray
+    pub colors: Vec<Color>,
+
+    /// Offset of each slice away from the center, relative to the radius of the pie
+    pub explode: Vec<f32>,
+
+    /// Radius of the hole relative to the radius of the pie. A value of zero gives a pie chart,
+    /// anything between zero and one a donut chart
+    pub inner_radius: f32,
+
+    /// Angle at which the first slice starts, in radians counter-clockwise from the positive x axis.
+    /// The slices follow each other clockwise
+    pub start_angle: f32,
+
+    /// If true, the names of the slices are written next to them
+    pub show_labels: bool,
+
+    /// If true, the share of each slice is written next to it, in percent
+    pub show_percentages: bool,
+}
+
+impl Default for PieData {
+    fn default() -> Self {
+        PieData {
+            values: vec![],
+            labels: vec![],
+            colors: vec![],
+            explode: vec![],
+            inner_radius: 0.0,
+            start_angle: std::f32::consts::FRAC_PI_2,
+            show_labels: true,
+            show_percentages: false,
+        }
+    }
+}
+
+impl PieData {
+    /// Start and end angles of each slice, going clockwise from `start_angle`.
+    pub fn angles(&self) -> Vec<(f32, f32)> {
+        let total = self.values.iter().map(|v| v.max(0.0)).sum::<f32>();
+        let mut angle = self.start_angle;
+
+        self.values
+            .iter()
+            .map(|v| {
+                let sweep = if total > 0.0 {
+                    v.max(0.0) / total * std::f32::consts::TAU
+                } else {
+                    0.0
+                };
+                angle -= sweep;
+                (angle, angle + sweep)
+            })
+            .collect()
+    }
+}
+
+/// One polygon of a radar chart.
+#[derive(Debug, Clone)]
+pub struct RadarSeries {
+    /// Name shown in the legend
+    pub name: String,
+
+    /// One value per axis of the chart
+    pub values: Vec<f32>,
+
+    pub color: Color,
+}
+
+/// Struct containing the data to be plotted and metaparameters of a radar (or spider) chart.
+/// It can be found in the `data.radar_groups` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct RadarData {
+    /// Name of each radial axis, going clockwise from the top
+    pub axes: Vec<String>,
+
+    /// The polygons to be compared
+    pub series: Vec<RadarSeries>,
+
+    /// Value at the outer ring. If None, the largest value of all the series is used
+    pub max_value: Option<f32>,
+
+    /// Number of concentric rings
+    pub num_rings: usize,
+
+    /// Thickness of the outline of the polygons
+    pub size: f32,
+
+    /// If true, the name and color of each series is shown in the top right corner of the canvas
+    pub show_legend: bool,
+}
+
+impl Default for RadarData {
+    fn default() -> Self {
+        RadarData {
+            axes: vec![],
+            series: vec![],
+            max_value: None,
+            num_rings: 4,
+            size: 1.0,
+            show_legend: true,
+        }
+    }
+}
+
+impl RadarData {
+    /// Value at the outer ring. Always finite and positive: falls back to `1.0` if neither
+    /// [`RadarData::max_value`] nor the series give a positive value.
+    pub fn outer_value(&self) -> f32 {
+        let outer_value = self.max_value.filter(|v| v.is_finite() && *v > 0.0).unwrap_or_else(|| {
+            self.series
+                .iter()
+                .flat_map(|series| series.values.iter())
+                .copied()
+                .filter(|v| v.is_finite())
+                .fold(0.0, f32::max)
+        });
+        if outer_value > 0.0 {
+            outer_value
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Shape of an annotation, in plot coordinates. See [`Plot::annotateopt`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Annotation {
+    /// Text centered on a point
+    Text(Vec2, String),
+
+    /// Arrow going from the first point to the second one
+    Arrow(Vec2, Vec2),
+
+    /// Horizontal line across the canvas at the given `y`
+    HLine(f32),
+
+    /// Vertical line across the canvas at the given `x`
+    VLine(f32),
+
+    /// Shaded band between two values of `x`, across the whole height of the canvas
+    XSpan(f32, f32),
+
+    /// Shaded band between two values of `y`, across the whole width of the canvas
+    YSpan(f32, f32),
+
+    /// Rectangle between a lower and an upper corner
+    Rect(Vec2, Vec2),
+}
+
+/// Struct containing an annotation and its look. Annotations pan and zoom with the data.
+/// It can be found in the `data.annotations` sub-field of a [`Plot`].
+#[derive(Debug, Clone)]
+pub struct AnnotationData {
+    pub annotation: Annotation,
+
+    /// Color of the lines and text. Spans and filled rectangles use a quarter of its opacity
+    pub color: Color,
+
+    /// Thickness of the lines
+    pub size: f32,
+
+    /// Text shown next to a line, an arrow, a span or a rectangle
+    pub label: Option<String>,
+
+    /// If true, a rectangle is filled in addition to its outline
+    pub fill: bool,
+
+    /// If true, the annotation stays on the edge of the canvas when it is out of view, for instance to
+    /// keep an event marker visible while scrolling away from it
+    pub clamp: bool,
+
+    /// Y axis the coordinates refer to
+    pub y_axis: YAxis,
+}
+
+impl Default for AnnotationData {
+    fn default() -> Self {
+        AnnotationData {
+            annotation: Annotation::HLine(0.0),
+            color: Color::srgba(0.6, 0.6, 0.6, 1.0),
+            size: 1.0,
+            label: None,
+            fill: false,
+            clamp: false,
+            y_axis: YAxis::Left,
         }
     }
 }
@@ -336,6 +1046,14 @@ pub struct PlotData {
     pub marker_groups: Vec<MarkerData>,
     pub segment_groups: Vec<SegmentData>,
     pub bezier_groups: Vec<BezierData>,
+    pub contour_groups: Vec<ContourData>,
+    pub quiver_groups: Vec<QuiverData>,
+    pub candlestick_groups: Vec<CandlestickData>,
+    pub stem_groups: Vec<StemData>,
+    pub stacked_area_groups: Vec<StackedAreaData>,
+    pub pie_groups: Vec<PieData>,
+    pub radar_groups: Vec<RadarData>,
+    pub annotations: Vec<AnnotationData>,
 }
 
 impl Default for PlotData {
@@ -344,6 +1062,14 @@ impl Default for PlotData {
             marker_groups: Vec::new(),
             segment_groups: Vec::new(),
             bezier_groups: Vec::new(),
+            contour_groups: Vec::new(),
+            quiver_groups: Vec::new(),
+            candlestick_groups: Vec::new(),
+            stem_groups: Vec::new(),
+            stacked_area_groups: Vec::new(),
+            pie_groups: Vec::new(),
+            radar_groups: Vec::new(),
+            annotations: Vec::new(),
         }
     }
 }
@@ -386,8 +1112,9 @@ impl MarkerStyle {
 pub enum LineStyle {
     None,
     Solid,
+    /// Dashes a few times as long as the line is thick
+    Dashed,
     // // unimplemented
-    // Dashed,
     // Dotted,
     // DashDot,
     // DashDotDot,
@@ -398,7 +1125,7 @@ impl LineStyle {
         match self {
             LineStyle::None => -1,
             LineStyle::Solid => 0,
-            // LineStyle::Dashed => 1,
+            LineStyle::Dashed => 1,
             // LineStyle::Dotted => 2,
             // LineStyle::DashDot => 3,
             // LineStyle::DashDotDot => 4,
@@ -419,7 +1146,7 @@ pub enum Opt {
     /// Thickness of a curve or segment. Shared between [`Plot::plotopt_func`]` and [`Plot::plotopt`]
     Size(f32),
 
-    /// Either [`LineStyle::None`] or [`LineStyle::Solid`]. The former can be used to
+    /// [`LineStyle::None`], [`LineStyle::Solid`] or [`LineStyle::Dashed`]. The former can be used to
     /// avoid spawning either the segments or the bezier curves, depending on the type of plot.
     LineStyle(LineStyle),
 
@@ -450,22 +1177,180 @@ pub enum Opt {
 
     /// If true, the markers are displayed with a black border.
     Contour(bool),
-}
 
-pub type PlotId = u32;
+    /// If true, the bands between consecutive contour levels are filled.
+    /// Works with [`Plot::contouropt`] only.
+    Fill(bool),
 
-#[derive(Component, TypePath, Debug, Clone, Default)]
-pub struct PlotIdComponent(pub PlotId);
+    /// If true, the value of each contour level is written along its iso-line.
+    /// Works with [`Plot::contouropt`] only.
+    Labels(bool),
 
-#[derive(Resource, TypePath, Debug, Clone, Default)]
-pub struct PlotMap {
-    pub plots: HashMap<PlotId, Plot>,
-}
+    /// Multiplies the vectors of a quiver plot to obtain the length of the arrows in plot coordinates.
+    /// Works with [`Plot::quiveropt`] and [`Plot::quiveropt_func`] only.
+    ArrowScale(f32),
 
-impl PlotMap {
-    pub fn get(&self, id: &u32) -> Option<&Plot> {
-        self.plots.get(id)
-    }
+    /// If true, the arrows of a quiver plot are colored according to their length.
+    /// Works with [`Plot::quiveropt`] and [`Plot::quiveropt_func`] only.
+    ColorByMagnitude(bool),
+
+    /// Color of the rising candles. Works with [`Plot::candlestickopt`] only.
+    UpColor(Color),
+
+    /// Color of the falling candles. Works with [`Plot::candlestickopt`] only.
+    DownColor(Color),
+
+    /// If true, the volumes are shown as bars below the candles. Works with [`Plot::candlestickopt`] only.
+    Volume(bool),
+
+    /// Draws a regular plot as a staircase. Works with [`Plot::plotopt`] only.
+    Step(StepMode),
+
+    /// Value of `y` from which the stems start. Works with [`Plot::stemopt`] only.
+    Baseline(f32),
+
+    /// If true, the layers of a stacked area chart sum up to 100 at every x value.
+    /// Works with [`Plot::stacked_areaopt`] only.
+    Normalize(bool),
+
+    /// Radius of the hole of a donut chart relative to its outer radius. Works with [`Plot::pieopt`] only.
+    InnerRadius(f32),
+
+    /// Pulls the slice with the given index away from the center, by the given fraction of the radius.
+    /// Works with [`Plot::pieopt`] only.
+    Explode(usize, f32),
+
+    /// Angle at which the first slice starts, in radians. Works with [`Plot::pieopt`] only.
+    StartAngle(f32),
+
+    /// If true, the share of each slice is written next to it. Works with [`Plot::pieopt`] only.
+    Percentages(bool),
+
+    /// Value at the outer ring of a radar chart. Works with [`Plot::radaropt`] only.
+    MaxValue(f32),
+
+    /// Number of concentric rings of a radar chart. Works with [`Plot::radaropt`] only.
+    Rings(usize),
+
+    /// If true, a legend with the name and color of each series is shown. Works with [`Plot::radaropt`] only.
+    Legend(bool),
+
+    /// Binds the series to the main (left) or secondary (right) y axis. Works with [`Plot::plotopt`],
+    /// [`Plot::stemopt`], [`Plot::candlestickopt`] and [`Plot::stacked_areaopt`].
+    YAxis(YAxis),
+
+    /// Name of the series, shown in the hover tooltips. Works with [`Plot::plotopt`],
+    /// [`Plot::stemopt`] and [`Plot::candlestickopt`].
+    Name(String),
+
+    /// Text shown next to an annotation. Works with [`Plot::annotateopt`] only.
+    Label(String),
+
+    /// If true, an annotation out of view stays on the edge of the canvas. Works with [`Plot::annotateopt`] only.
+    Clamp(bool),
+}
+
+pub type PlotId = u32;
+
+/// Series of a plot, made of the kind of series and its index in the corresponding field of [`PlotData`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeriesId {
+    /// Index in `data.segment_groups`
+    Segments(usize),
+    /// Index in `data.marker_groups`
+    Markers(usize),
+    /// Index in `data.stem_groups`
+    Stems(usize),
+    /// Index in `data.candlestick_groups`. The point of a candle is its closing price.
+    Candles(usize),
+}
+
+/// Sent when the user clicks on a data point (a marker, a line vertex, the top of a stem or the close of a candle).
+/// The point is the nearest one to the cursor within the `tooltip_radius` of the plot.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct PlotPointClicked {
+    pub plot_id: PlotId,
+    pub series: SeriesId,
+
+    /// Index of the point in the data of the series
+    pub index: usize,
+
+    /// Coordinates of the point, with y on the axis the series is bound to
+    pub position: Vec2,
+}
+
+/// Sent when the cursor starts hovering a data point. See [`PlotPointClicked`] for the fields.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct PlotPointHoverEnter {
+    pub plot_id: PlotId,
+    pub series: SeriesId,
+    pub index: usize,
+    pub position: Vec2,
+}
+
+/// Sent when the cursor stops hovering a data point. See [`PlotPointClicked`] for the fields.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct PlotPointHoverLeave {
+    pub plot_id: PlotId,
+    pub series: SeriesId,
+    pub index: usize,
+    pub position: Vec2,
+}
+
+/// Data point under the cursor, passed to the `tooltip_formatter` of a [`Plot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoveredPoint {
+    pub plot_id: PlotId,
+    pub series: SeriesId,
+
+    /// Name of the series given with [`Opt::Name`], if any
+    pub series_name: Option<String>,
+
+    /// Index of the point in the data of the series
+    pub index: usize,
+
+    /// Coordinates of the point, with y on the axis the series is bound to
+    pub position: Vec2,
+}
+
+/// Builds the text of a tooltip from the hovered point. Being a closure, it can capture state such as
+/// units or labels:
+///
+/// ```ignore
+/// let unit = "s".to_string();
+/// plot.tooltip_formatter = Some(TooltipFormatter::new(move |point| format!("{:.2} {}", point.position.y, unit)));
+/// ```
+#[derive(Clone)]
+pub struct TooltipFormatter(pub Arc<dyn Fn(&HoveredPoint) -> String + Send + Sync>);
+
+impl TooltipFormatter {
+    pub fn new(formatter: impl Fn(&HoveredPoint) -> String + Send + Sync + 'static) -> Self {
+        TooltipFormatter(Arc::new(formatter))
+    }
+
+    pub fn format(&self, point: &HoveredPoint) -> String {
+        (self.0)(point)
+    }
+}
+
+impl std::fmt::Debug for TooltipFormatter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TooltipFormatter(..)")
+    }
+}
+
+#[derive(Component, TypePath, Debug, Clone, Default)]
+pub struct PlotIdComponent(pub PlotId);
+
+#[derive(Resource, TypePath, Debug, Clone, Default)]
+pub struct PlotMap {
+    pub plots: HashMap<PlotId, Plot>,
+}
+
+impl PlotMap {
+    pub fn get(&self, id: &u32) -> Option<&Plot> {
+        self.plots.get(id)
+    }
 
     pub fn get_mut(&mut self, id: &u32) -> Option<&mut Plot> {
         self.plots.get_mut(id)
@@ -475,13 +1360,15 @@ impl PlotMap {
         self.plots.insert(id, plot);
     }
 
-    pub fn add(&mut self, mut plot: Plot) {
+    /// Adds the plot to the map under a newly generated id, which is returned.
+    pub fn add(&mut self, mut plot: Plot) -> PlotId {
         // generate a new id
         use rand::Rng;
         let mut rng = rand::thread_rng();
         let id = rng.gen();
         plot.id = id;
         self.plots.insert(id, plot);
+        id
     }
 }
 
@@ -508,6 +1395,12 @@ pub struct Plot {
     /// Size of the graph in pixels
     pub canvas_size: Vec2,
 
+    /// The canvas cannot be made smaller than this by dragging its corners and edges
+    pub min_canvas_size: Vec2,
+
+    /// If true, resizing the canvas with the mouse keeps its aspect ratio
+    pub lock_aspect: bool,
+
     /// Color of even tiles
     pub background_color1: Color,
 
@@ -553,6 +1446,19 @@ pub struct Plot {
     /// Number of significant digits for the target coordinates
     pub target_significant_digits: usize,
 
+    /// Interactions enabled on the plot, for instance to prevent it from reacting to the controls of a game
+    pub interaction: PlotInteraction,
+
+    /// If true, hovering near a data point highlights it and shows a tooltip (when a [`TickLabelFont`] is given)
+    pub show_tooltips: bool,
+
+    /// Maximum distance in pixels between the cursor and a data point for the point to be hovered
+    pub tooltip_radius: f32,
+
+    /// Custom text for the tooltips. If None, the tooltip shows the name of the series, the index of the
+    /// point and its coordinates
+    pub tooltip_formatter: Option<TooltipFormatter>,
+
     /// Axes are shown by default
     pub show_axes: bool,
 
@@ -562,10 +1468,22 @@ pub struct Plot {
     /// Contains the data and metaparameters needed for drawing each kind of plot
     pub data: PlotData,
 
+    /// Automatic range of the axes, for instance to follow live data. Fixed by default
+    pub autoscale: Autoscale,
+
+    /// Right-hand y axis for the series plotted with `Opt::YAxis(YAxis::Right)`. See [`Plot::set_secondary_bounds`]
+    pub secondary_axis: Option<SecondaryAxis>,
+
+    /// If set, the plot is drawn offscreen into an image instead of the window. See [`Plot::render_to_texture`]
+    pub render_target: Option<PlotTexture>,
+
     pub(crate) target_position: Vec2,
     pub(crate) target_toggle: bool,
+    pub(crate) pinned_targets: Vec<Vec2>,
     pub(crate) bounds: PlotCanvasBounds,
+    pub(crate) bounds_set_by_user: bool,
     pub(crate) bezier_dummy: f32,
+    pub(crate) view_history: ViewHistory,
     pub(crate) do_spawn_plot: bool,
 }
 
@@ -588,6 +1506,7 @@ impl Default for Plot {
                 up: Vec2::new(1.2, 1.2),
                 lo: Vec2::new(-0.2, -0.2),
             },
+            bounds_set_by_user: false,
 
             time: 0.0,
             zoom: 1.0,
@@ -598,6 +1517,8 @@ impl Default for Plot {
             background_color1: Color::srgba(0.048 * mult, 0.00468 * mult, 0.0744 * mult, 1.0),
             background_color2: Color::srgba(0.0244 * mult, 0.0023 * mult, 0.0372 * mult, 1.0),
             canvas_size: size.clone(),
+            min_canvas_size: Vec2::new(150.0, 100.0),
+            lock_aspect: false,
             outer_border: Vec2::new(0.03 * size.y / size.x, 0.03),
             zero_world: Vec2::new(0.0, 0.0),
 
@@ -612,14 +1533,23 @@ impl Default for Plot {
             target_label_color: Color::srgba(0.5, 0.5, 0.5, 1.0),
             target_color: Color::srgba(0.5, 0.5, 0.5, 1.0),
             target_position: Vec2::new(0.0, 0.0),
+            pinned_targets: vec![],
             target_significant_digits: 2,
+            interaction: PlotInteraction::default(),
+            show_tooltips: true,
+            tooltip_radius: 15.0,
+            tooltip_formatter: None,
 
             canvas_position: Vec2::ZERO,
 
             data: PlotData::default(),
+            secondary_axis: None,
+            autoscale: Autoscale::default(),
+            render_target: None,
 
             bezier_num_points: 100,
             bezier_dummy: 0.0,
+            view_history: ViewHistory::default(),
 
             do_spawn_plot: true,
         };
@@ -659,6 +1589,17 @@ impl Plot {
                         data.mech = *mech;
                     }
 
+                    Opt::Step(step) => {
+                        data.step = Some(*step);
+                    }
+
+                    Opt::YAxis(y_axis) => {
+                        data.y_axis = *y_axis;
+                    }
+                    Opt::Name(name) => {
+                        data.name = Some(name.clone());
+                    }
+
                     _ => {}
                 }
             }
@@ -706,12 +1647,29 @@ impl Plot {
                     Opt::Contour(cont) => {
                         data.draw_contour = *cont;
                     }
+                    Opt::YAxis(y_axis) => {
+                        data.y_axis = *y_axis;
+                    }
+                    Opt::Name(name) => {
+                        data.name = Some(name.clone());
+                    }
                     _ => {}
                 }
             }
 
             self.data.marker_groups.push(data);
         }
+
+        // a series on the right axis gets an axis fitted to its data if there is none yet
+        if options.contains(&Opt::YAxis(YAxis::Right)) && self.secondary_axis.is_none() {
+            let data = &data_in_plot_format.data;
+            let lo_y = data.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
+            let up_y = data.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
+            if lo_y.is_finite() && up_y.is_finite() {
+                let dy = ((up_y - lo_y) * 0.1).max(0.5);
+                self.set_secondary_bounds(lo_y - dy, up_y + dy);
+            }
+        }
     }
 
     /// Quickly plot data points using segments to connect consecutive points. Takes any type
@@ -733,7 +1691,7 @@ impl Plot {
         let dx = (up_x - lo_x).abs() * 0.1;
         let dy = (up_y - lo_y).abs() * 0.1;
 
-        self.set_bounds(Vec2::new(lo_x - dx, lo_y - dy), Vec2::new(up_x + dx, up_y + dy));
+        self.fit_bounds(Vec2::new(lo_x - dx, lo_y - dy), Vec2::new(up_x + dx, up_y + dy), YAxis::Left);
 
         let new_data = SegmentData {
             data: pf.data,
@@ -741,8 +1699,6 @@ impl Plot {
         };
 
         self.data.segment_groups.push(new_data);
-
-        println!("Segment added");
     }
 
     /// Quickly plot data points using markers (scatter plot).
@@ -763,7 +1719,7 @@ impl Plot {
         let dx = (up_x - lo_x).abs() * 0.1;
         let dy = (up_y - lo_y).abs() * 0.1;
 
-        self.set_bounds(Vec2::new(lo_x - dx, lo_y - dy), Vec2::new(up_x + dx, up_y + dy));
+        self.fit_bounds(Vec2::new(lo_x - dx, lo_y - dy), Vec2::new(up_x + dx, up_y + dy), YAxis::Left);
 
         let new_data = MarkerData {
             data: data,
@@ -773,6 +1729,256 @@ impl Plot {
         self.data.marker_groups.push(new_data);
     }
 
+    /// Stem plot: a vertical line from `y = 0` to each data point, topped with a marker. Takes any type
+    /// that implements [`Plotable`], namely `Vec<Vec2>`, `Vec<(f64, f64)>`, `Vec<f32>`, ...
+    pub fn stem(&mut self, v: impl Plotable) {
+        self.stemopt(v, vec![]);
+    }
+
+    /// Stem plot with options. [`Opt::Baseline`] sets the value of `y` from which the stems start,
+    /// [`Opt::Color`] and [`Opt::Size`] apply to the stems and the `Marker*` options to the markers.
+    pub fn stemopt(&mut self, v: impl Plotable, options: Vec<Opt>) {
+        //
+        let mut data = StemData {
+            data: v.into_plot_format().data,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::Color(col) => {
+                    data.color = *col;
+                }
+
+                Opt::Size(si) => {
+                    data.size = *si;
+                }
+
+                Opt::Baseline(baseline) => {
+                    data.baseline = *baseline;
+                }
+
+                Opt::MarkerColor(col) => {
+                    data.marker.color = *col;
+                }
+
+                Opt::MarkerSize(si) => {
+                    data.marker.size = si.clamp(0.2, 2.0);
+                }
+
+                Opt::MarkerStyle(style) => {
+                    data.marker.marker_style = style.clone();
+                }
+
+                Opt::YAxis(y_axis) => {
+                    data.y_axis = *y_axis;
+                }
+
+                Opt::Name(name) => {
+                    data.name = Some(name.clone());
+                }
+
+                _ => {}
+            }
+        }
+
+        let data_points = &data.data;
+        if !data_points.is_empty() {
+            let lo_x = data_points.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
+            let up_x = data_points.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
+            let lo_y = data_points.iter().map(|p| p.y).fold(data.baseline, f32::min);
+            let up_y = data_points.iter().map(|p| p.y).fold(data.baseline, f32::max);
+
+            let dx = ((up_x - lo_x) * 0.1).max(0.5);
+            let dy = ((up_y - lo_y) * 0.1).max(0.5);
+
+            self.fit_bounds(
+                Vec2::new(lo_x - dx, lo_y - dy),
+                Vec2::new(up_x + dx, up_y + dy),
+                data.y_axis,
+            );
+        }
+
+        self.data.stem_groups.push(data);
+    }
+
+    /// Stacked area chart: each layer of `layers` is stacked on top of the previous ones and
+    /// filled with its own color from the [`ColorPalette`]. The values are expected to be positive:
+    /// negative values are clamped to zero.
+    pub fn stacked_area(&mut self, xs: Vec<f32>, layers: Vec<Vec<f32>>) {
+        self.stacked_areaopt(xs, layers, vec![]);
+    }
+
+    /// Stacked area chart with options. Use [`Opt::Normalize`] to show the share of each layer in percent,
+    /// [`Opt::LineStyle`] and [`Opt::Size`] for the lines on top of the layers and [`Opt::YAxis`] to draw
+    /// the chart against the secondary axis.
+    pub fn stacked_areaopt(&mut self, xs: Vec<f32>, layers: Vec<Vec<f32>>, options: Vec<Opt>) {
+        //
+        let colors = palette_colors(layers.len());
+
+        let mut data = StackedAreaData {
+            xs,
+            layers,
+            colors,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::Normalize(normalize) => {
+                    data.normalize = *normalize;
+                }
+
+                Opt::Size(si) => {
+                    data.size = *si;
+                }
+
+                Opt::LineStyle(style) => {
+                    data.line_style = style.clone();
+                }
+
+                Opt::YAxis(y_axis) => {
+                    data.y_axis = *y_axis;
+                }
+
+                _ => {}
+            }
+        }
+
+        let lo_x = data.xs.iter().copied().fold(f32::INFINITY, f32::min);
+        let up_x = data.xs.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let stacked = data.stacked();
+        let lo_y = stacked.iter().flatten().copied().fold(0.0, f32::min);
+        let up_y = stacked.iter().flatten().copied().fold(0.0, f32::max);
+
+        if lo_x < up_x && lo_y < up_y {
+            let dy = (up_y - lo_y) * 0.05;
+            self.fit_bounds(Vec2::new(lo_x, lo_y - dy), Vec2::new(up_x, up_y + dy), data.y_axis);
+        }
+
+        self.data.stacked_area_groups.push(data);
+    }
+
+    /// Pie chart made of named slices. The axes, grid and tick labels of the canvas are hidden.
+    pub fn pie<S: Into<String>>(&mut self, slices: Vec<(S, f32)>) {
+        self.pieopt(slices, vec![]);
+    }
+
+    /// Pie chart with options. Use [`Opt::InnerRadius`] for a donut chart, [`Opt::Explode`] to pull
+    /// slices out, [`Opt::StartAngle`] to rotate the chart, [`Opt::Percentages`] to write the share
+    /// of each slice and [`Opt::Labels`] to show or hide the names.
+    pub fn pieopt<S: Into<String>>(&mut self, slices: Vec<(S, f32)>, options: Vec<Opt>) {
+        //
+        let (labels, values): (Vec<String>, Vec<f32>) =
+            slices.into_iter().map(|(label, value)| (label.into(), value)).unzip();
+
+        let mut data = PieData {
+            colors: palette_colors(values.len()),
+            explode: vec![0.0; values.len()],
+            values,
+            labels,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::InnerRadius(radius) => {
+                    data.inner_radius = radius.clamp(0.0, 0.95);
+                }
+
+                Opt::Explode(index, offset) => {
+                    if let Some(explode) = data.explode.get_mut(*index) {
+                        *explode = offset.max(0.0);
+                    }
+                }
+
+                Opt::StartAngle(angle) => {
+                    data.start_angle = *angle;
+                }
+
+                Opt::Labels(show_labels) => {
+                    data.show_labels = *show_labels;
+                }
+
+                Opt::Percentages(show_percentages) => {
+                    data.show_percentages = *show_percentages;
+                }
+
+                _ => {}
+            }
+        }
+
+        // a pie chart has no use for cartesian axes
+        self.show_axes = false;
+        self.show_grid = false;
+        self.hide_tick_labels = true;
+
+        self.data.pie_groups.push(data);
+    }
+
+    /// Radar chart comparing several series of values along the named `axes`. Each series is a
+    /// name together with one value per axis. The axes, grid and tick labels of the canvas are hidden.
+    pub fn radar<S: Into<String>, T: Into<String>>(&mut self, axes: Vec<S>, series: Vec<(T, Vec<f32>)>) {
+        self.radaropt(axes, series, vec![]);
+    }
+
+    /// Radar chart with options. Use [`Opt::MaxValue`] to fix the value at the outer ring,
+    /// [`Opt::Rings`] for the number of rings and [`Opt::Legend`] to show or hide the legend.
+    /// Non-finite values are drawn at the center.
+    pub fn radaropt<S: Into<String>, T: Into<String>>(
+        &mut self,
+        axes: Vec<S>,
+        series: Vec<(T, Vec<f32>)>,
+        options: Vec<Opt>,
+    ) {
+        //
+        let colors = palette_colors(series.len());
+
+        let mut data = RadarData {
+            axes: axes.into_iter().map(|axis| axis.into()).collect(),
+            series: series
+                .into_iter()
+                .zip(colors)
+                .map(|((name, values), color)| RadarSeries {
+                    name: name.into(),
+                    // a missing value sits at the center, so the vertices stay aligned with their axes
+                    values: values.into_iter().map(|v| if v.is_finite() { v } else { 0.0 }).collect(),
+                    color,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::MaxValue(max_value) => {
+                    data.max_value = Some(*max_value);
+                }
+
+                Opt::Rings(num_rings) => {
+                    data.num_rings = (*num_rings).max(1);
+                }
+
+                Opt::Size(si) => {
+                    data.size = *si;
+                }
+
+                Opt::Legend(show_legend) => {
+                    data.show_legend = *show_legend;
+                }
+
+                _ => {}
+            }
+        }
+
+        // a radar chart has no use for cartesian axes
+        self.show_axes = false;
+        self.show_grid = false;
+        self.hide_tick_labels = true;
+
+        self.data.radar_groups.push(data);
+    }
+
     /// Quickly plot a function by providing said function. Defaults to a range on the both axes from `-0.2` to `1.2`.
     pub fn plot_func(&mut self, f: fn(f32, f32) -> f32) {
         //
@@ -815,33 +2021,394 @@ impl Plot {
                 }
 
                 Opt::MarkerStyle(_) => {
-                    eprintln!("MarkerStyle is not a valid option for segments");
+                    eprintln!("MarkerStyle is not a valid option for func plots");
                 }
 
                 Opt::MarkerInnerPointColor(_) => {
-                    eprintln!("MarkerInnerPointColor is not a valid option for segments");
+                    eprintln!("MarkerInnerPointColor is not a valid option for func plots");
                 }
 
                 Opt::Contour(_) => {
-                    println!("Contour is not a valid option for segments");
+                    eprintln!("Contour is not a valid option for func plots");
                 }
 
                 Opt::NumPoints(_) => {
-                    eprintln!("NumPoints is not a valid option for segments");
+                    eprintln!("NumPoints is not a valid option for func plots");
                 }
 
                 Opt::MarkerColor(_) => {
-                    eprintln!("MarkerColor is not a valid option for segments");
+                    eprintln!("MarkerColor is not a valid option for func plots");
                 }
 
                 Opt::MarkerSize(_) => {
-                    eprintln!("MarkerSize is not a valid option for segments");
+                    eprintln!("MarkerSize is not a valid option for func plots");
+                }
+
+                Opt::Fill(_) => {
+                    eprintln!("Fill is not a valid option for func plots");
+                }
+
+                Opt::Labels(_) => {
+                    eprintln!("Labels is not a valid option for func plots");
+                }
+
+                Opt::ArrowScale(_) => {
+                    eprintln!("ArrowScale is not a valid option for func plots");
+                }
+
+                Opt::ColorByMagnitude(_) => {
+                    eprintln!("ColorByMagnitude is not a valid option for func plots");
+                }
+
+                Opt::UpColor(_) => {
+                    eprintln!("UpColor is not a valid option for func plots");
+                }
+
+                Opt::DownColor(_) => {
+                    eprintln!("DownColor is not a valid option for func plots");
+                }
+
+                Opt::Volume(_) => {
+                    eprintln!("Volume is not a valid option for func plots");
+                }
+
+                Opt::Step(_) => {
+                    eprintln!("Step is not a valid option for func plots");
+                }
+
+                Opt::Baseline(_) => {
+                    eprintln!("Baseline is not a valid option for func plots");
+                }
+
+                Opt::Normalize(_) => {
+                    eprintln!("Normalize is not a valid option for func plots");
+                }
+
+                Opt::InnerRadius(_) => {
+                    eprintln!("InnerRadius is not a valid option for func plots");
+                }
+
+                Opt::Explode(_, _) => {
+                    eprintln!("Explode is not a valid option for func plots");
+                }
+
+                Opt::StartAngle(_) => {
+                    eprintln!("StartAngle is not a valid option for func plots");
+                }
+
+                Opt::Percentages(_) => {
+                    eprintln!("Percentages is not a valid option for func plots");
+                }
+
+                Opt::MaxValue(_) => {
+                    eprintln!("MaxValue is not a valid option for func plots");
+                }
+
+                Opt::Rings(_) => {
+                    eprintln!("Rings is not a valid option for func plots");
+                }
+
+                Opt::Legend(_) => {
+                    eprintln!("Legend is not a valid option for func plots");
+                }
+
+                Opt::YAxis(_) => {
+                    eprintln!("YAxis is not a valid option for func plots");
+                }
+
+                Opt::Name(_) => {
+                    eprintln!("Name is not a valid option for func plots");
+                }
+
+                Opt::Label(_) => {
+                    eprintln!("Label is not a valid option for func plots");
+                }
+
+                Opt::Clamp(_) => {
+                    eprintln!("Clamp is not a valid option for func plots");
                 } // _ => {},
             }
         }
         self.data.bezier_groups.push(data);
     }
 
+    /// Plot the iso-lines of a scalar field at the given `levels`. If the field is a [`ContourField::Grid`],
+    /// the bounds of the plot are set to the extent of the grid.
+    pub fn contour(&mut self, field: ContourField, levels: Vec<f32>) {
+        self.contouropt(field, levels, vec![]);
+    }
+
+    /// Plot the iso-lines of a scalar field by providing said field, the levels and options.
+    /// Use [`Opt::Fill`] to fill the bands between levels and [`Opt::Labels`] to write the levels inline.
+    pub fn contouropt(&mut self, field: ContourField, mut levels: Vec<f32>, options: Vec<Opt>) {
+        //
+        levels.retain(|level| level.is_finite());
+        levels.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        levels.dedup();
+
+        if let ContourField::Grid { lo, up, .. } = &field {
+            self.fit_bounds(*lo, *up, YAxis::Left);
+        }
+
+        let mut data = ContourData {
+            field,
+            levels,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::Color(col) => {
+                    data.color = *col;
+                }
+
+                Opt::Size(si) => {
+                    data.size = *si;
+                }
+
+                Opt::LineStyle(style) => {
+                    data.line_style = style.clone();
+                }
+
+                Opt::NumPoints(num_points) => {
+                    data.num_points = (*num_points).max(2);
+                }
+
+                Opt::Fill(fill) => {
+                    data.fill = *fill;
+                }
+
+                Opt::Labels(labels) => {
+                    data.show_labels = *labels;
+                }
+
+                _ => {}
+            }
+        }
+
+        self.data.contour_groups.push(data);
+    }
+
+    /// Plot a vector field as arrows. The `positions` and `vectors` can be of any type
+    /// that implements [`Plotable`] and are paired element by element.
+    pub fn quiver(&mut self, positions: impl Plotable, vectors: impl Plotable) {
+        self.quiveropt(positions, vectors, vec![]);
+    }
+
+    /// Plot a vector field as arrows by providing the positions, vectors and options.
+    pub fn quiveropt(&mut self, positions: impl Plotable, vectors: impl Plotable, options: Vec<Opt>) {
+        //
+        let data = positions
+            .into_plot_format()
+            .data
+            .into_iter()
+            .zip(vectors.into_plot_format().data)
+            .collect::<Vec<(Vec2, Vec2)>>();
+
+        self.push_quiver(QuiverField::Data(data), options);
+    }
+
+    /// Plot a vector field given by an explicit function of `x` and `y`. The function is sampled
+    /// on a regular grid over the visible range of the axes.
+    pub fn quiver_func(&mut self, f: fn(f32, f32) -> Vec2) {
+        self.quiveropt_func(f, vec![]);
+    }
+
+    /// Plot a vector field given by an explicit function of `x` and `y`, with options.
+    /// [`Opt::NumPoints`] sets the number of samples per axis.
+    pub fn quiveropt_func(&mut self, f: fn(f32, f32) -> Vec2, options: Vec<Opt>) {
+        self.push_quiver(QuiverField::Function(f), options);
+    }
+
+    fn push_quiver(&mut self, field: QuiverField, options: Vec<Opt>) {
+        let mut data = QuiverData {
+            field,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::Color(col) => {
+                    data.color = *col;
+                }
+
+                Opt::Size(si) => {
+                    data.size = *si;
+                }
+
+                Opt::NumPoints(num_points) => {
+                    data.num_points = (*num_points).max(2);
+                }
+
+                Opt::ArrowScale(scale) => {
+                    data.scale = Some(*scale);
+                }
+
+                Opt::ColorByMagnitude(by_magnitude) => {
+                    data.color_by_magnitude = *by_magnitude;
+                }
+
+                _ => {}
+            }
+        }
+
+        self.data.quiver_groups.push(data);
+    }
+
+    /// Plot a financial series as candles. Takes any type that implements [`PlotableOhlc`], namely
+    /// `Vec<(f32, f32, f32, f32, f32)>` for `(time, open, high, low, close)`, the same with a sixth
+    /// element for the volume, or `Vec<Candle>`.
+    pub fn candlestick(&mut self, v: impl PlotableOhlc) {
+        self.candlestickopt(v, vec![]);
+    }
+
+    /// Plot a financial series as candles with options. [`Opt::Size`] sets the width of the candles
+    /// relative to the time between two candles, and [`Opt::Volume`] adds a strip of volume bars.
+    pub fn candlestickopt(&mut self, v: impl PlotableOhlc, options: Vec<Opt>) {
+        //
+        let mut candles = v.to_candles();
+        candles.retain(|c| [c.time, c.open, c.high, c.low, c.close].iter().all(|v| v.is_finite()));
+        for candle in candles.iter_mut() {
+            candle.volume = candle.volume.filter(|volume| volume.is_finite());
+        }
+        candles.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+
+        let mut data = CandlestickData {
+            data: candles,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::UpColor(col) => {
+                    data.up_color = *col;
+                }
+
+                Opt::DownColor(col) => {
+                    data.down_color = *col;
+                }
+
+                Opt::Size(si) => {
+                    data.size = si.clamp(0.05, 1.0);
+                }
+
+                Opt::Volume(show_volume) => {
+                    data.show_volume = *show_volume;
+                }
+
+                Opt::YAxis(y_axis) => {
+                    data.y_axis = *y_axis;
+                }
+
+                Opt::Name(name) => {
+                    data.name = Some(name.clone());
+                }
+
+                _ => {}
+            }
+        }
+
+        if let (Some(first), Some(last)) = (data.data.first(), data.data.last()) {
+            let lo_y = data.data.iter().map(|c| c.low).fold(f32::INFINITY, f32::min);
+            let up_y = data.data.iter().map(|c| c.high).fold(f32::NEG_INFINITY, f32::max);
+
+            let dx = ((last.time - first.time) * 0.05).max(0.5);
+            let dy = ((up_y - lo_y) * 0.1).max(0.5);
+
+            // leave room for the volume strip at the bottom of the canvas
+            let strip = if data.show_volume {
+                (up_y - lo_y + 2.0 * dy) * data.volume_height / (1.0 - data.volume_height)
+            } else {
+                0.0
+            };
+
+            self.fit_bounds(
+                Vec2::new(first.time - dx, lo_y - dy - strip),
+                Vec2::new(last.time + dx, up_y + dy),
+                data.y_axis,
+            );
+        }
+
+        self.data.candlestick_groups.push(data);
+    }
+
+    /// Adds an annotation (text, arrow, reference line, span or rectangle) that pans and zooms with the data.
+    pub fn annotate(&mut self, annotation: Annotation) {
+        self.annotateopt(annotation, vec![]);
+    }
+
+    /// Annotation with options. [`Opt::Color`] and [`Opt::Size`] set the color and the thickness of
+    /// the lines, [`Opt::Label`] adds a text next to the annotation, [`Opt::Fill`] fills a rectangle,
+    /// [`Opt::Clamp`] keeps the annotation on the edge of the canvas when it is out of view and
+    /// [`Opt::YAxis`] binds it to the secondary axis.
+    ///
+    /// ```ignore
+    /// plot.annotateopt(Annotation::VLine(42.0), vec![Opt::Label("boss spawned".to_string()), Opt::Clamp(true)]);
+    /// ```
+    pub fn annotateopt(&mut self, annotation: Annotation, options: Vec<Opt>) {
+        let mut data = AnnotationData {
+            annotation,
+            ..Default::default()
+        };
+
+        for option in options.iter() {
+            match option {
+                Opt::Color(col) => {
+                    data.color = *col;
+                }
+
+                Opt::Size(si) => {
+                    data.size = *si;
+                }
+
+                Opt::Label(label) => {
+                    data.label = Some(label.clone());
+                }
+
+                Opt::Fill(fill) => {
+                    data.fill = *fill;
+                }
+
+                Opt::Clamp(clamp) => {
+                    data.clamp = *clamp;
+                }
+
+                Opt::YAxis(y_axis) => {
+                    data.y_axis = *y_axis;
+                }
+
+                _ => {}
+            }
+        }
+
+        self.data.annotations.push(data);
+    }
+
+    /// Horizontal reference line at `y`.
+    pub fn axhline(&mut self, y: f32) {
+        self.annotate(Annotation::HLine(y));
+    }
+
+    /// Vertical reference line at `x`.
+    pub fn axvline(&mut self, x: f32) {
+        self.annotate(Annotation::VLine(x));
+    }
+
+    /// Shaded band between `lo` and `up` along the y axis.
+    pub fn axhspan(&mut self, lo: f32, up: f32) {
+        self.annotate(Annotation::YSpan(lo, up));
+    }
+
+    /// Shaded band between `lo` and `up` along the x axis.
+    pub fn axvspan(&mut self, lo: f32, up: f32) {
+        self.annotate(Annotation::XSpan(lo, up));
+    }
+
+    /// Text centered on `position`, in plot coordinates. Requires a [`TickLabelFont`].
+    pub fn text(&mut self, position: Vec2, text: impl Into<String>) {
+        self.annotate(Annotation::Text(position, text.into()));
+    }
+
     fn make_canvas(&self) -> CanvasParams {
         let canvas = CanvasParams {
             position: self.canvas_position,
@@ -859,17 +2426,32 @@ impl Plot {
         self.bounds.up - self.bounds.lo
     }
 
-    pub(crate) fn zoom_axes(&mut self, direction: f32) {
+    /// Zooms out by 10% per wheel step in `direction`, or in by 10% per step if it is negative.
+    pub(crate) fn zoom_axes(&mut self, direction: f32, axes: BVec2) {
         let percent_factor = 10.0;
 
-        let multiplier = 1.0 + direction * percent_factor / 100.0;
+        // the steps are compounded, so that several steps in a frame cannot collapse the axes
+        let step = 1.0 + direction.signum() * percent_factor / 100.0;
+        let multiplier = step.powf(direction.abs());
+        let multipliers = Vec2::select(axes, Vec2::splat(multiplier), Vec2::ONE);
 
         let mouse_pos = self.plot_coord_mouse_pos;
+        self.autoscale.paused = true;
+
+        // the secondary axis is zoomed around the same point of the canvas
+        if let (Some(axis), true) = (self.secondary_axis.as_mut(), axes.y) {
+            let t = (mouse_pos.y - self.bounds.lo.y) / (self.bounds.up.y - self.bounds.lo.y);
+            let mouse_y = axis.lo + t * (axis.up - axis.lo);
+            axis.up = mouse_y + (axis.up - mouse_y) * multiplier;
+            axis.lo = mouse_y - (mouse_y - axis.lo) * multiplier;
+        }
 
-        self.bounds.up = self.plot_coord_mouse_pos + (self.bounds.up - mouse_pos) * multiplier;
-        self.bounds.lo = self.plot_coord_mouse_pos - (mouse_pos - self.bounds.lo) * multiplier;
+        self.bounds.up = self.plot_coord_mouse_pos + (self.bounds.up - mouse_pos) * multipliers;
+        self.bounds.lo = self.plot_coord_mouse_pos - (mouse_pos - self.bounds.lo) * multipliers;
 
-        self.zoom *= multiplier;
+        if axes.all() {
+            self.zoom *= multiplier;
+        }
     }
 
     pub(crate) fn move_axes(&mut self, mut mouse_delta: Vec2) {
@@ -877,9 +2459,16 @@ impl Plot {
         let mut axes = self.delta_axes();
         axes.x *= -1.0;
         let size = self.canvas_size / (1. + self.outer_border);
+        self.autoscale.paused = true;
 
         self.bounds.up += mouse_delta * axes / size;
         self.bounds.lo += mouse_delta * axes / size;
+
+        if let Some(axis) = self.secondary_axis.as_mut() {
+            let shift = mouse_delta.y * (axis.up - axis.lo) / size.y;
+            axis.up += shift;
+            axis.lo += shift;
+        }
     }
 
     // TODO: make a smarter tick period adjuster
@@ -901,11 +2490,20 @@ impl Plot {
     /// Override the default plot bounds: x axis goes from bounds.lo.x to bounds.up.x.
     /// Beware! The tick period is automatically adjusted. Changing the tick period before setting the bounds will not have the intended effect.
     /// The bounds must be set before the ticks.
+    /// Once set, the bounds are not fitted anymore to the series added afterwards (stems, candles,
+    /// stacked areas, contour grids).
     ///
     /// # Panics
     ///
     /// Panics if `lo.x >= up.x` or `lo.y >= up.y`.
     pub fn set_bounds(&mut self, lo: Vec2, up: Vec2) {
+        self.apply_bounds(lo, up);
+        self.bounds_set_by_user = true;
+    }
+
+    /// Same as [`Plot::set_bounds`], for the changes of range that do not come from the user code
+    /// (series fitting their data, view history, autoscale, box zoom).
+    pub(crate) fn apply_bounds(&mut self, lo: Vec2, up: Vec2) {
         if lo.x >= up.x {
             panic!("when using plot.set_bounds(), lo.x must be strictly less than up.x");
         } else if lo.y >= up.y {
@@ -915,59 +2513,111 @@ impl Plot {
         self.bounds = PlotCanvasBounds { lo, up };
 
         let delta = up - lo;
-        let exact_tick = delta / 10.0;
+        let tick_x = nice_tick_period(delta.x);
+        let tick_y = nice_tick_period(delta.y);
 
-        // find order of magnitude of dx
-        let order_x = exact_tick.x.log10().floor();
-        let mag_x = 10_f32.powf(order_x);
+        self.tick_period = Vec2::new(tick_x, tick_y);
 
-        let p1x = mag_x * 1.0;
-        let p2x = mag_x * 2.0;
-        let p5x = mag_x * 5.0;
+        self.compute_zeros();
+    }
 
-        let psx = [p1x, p2x, p5x];
+    /// Sets the bounds to the region between `lo` and `up`, and zooms the secondary axis (if any)
+    /// onto the same part of the canvas.
+    pub(crate) fn zoom_to_region(&mut self, lo: Vec2, up: Vec2) {
+        self.autoscale.paused = true;
 
-        let vx = vec![
-            (p1x - exact_tick.x).abs(),
-            (p2x - exact_tick.x).abs(),
-            (p5x - exact_tick.x).abs(),
-        ];
+        if let Some(axis) = self.secondary_axis.as_ref() {
+            let range = self.bounds.up.y - self.bounds.lo.y;
+            let t_lo = (lo.y - self.bounds.lo.y) / range;
+            let t_up = (up.y - self.bounds.lo.y) / range;
+            let (axis_lo, axis_up) = (axis.lo, axis.up);
 
-        use std::cmp::Ordering;
-        let min_x_index = vx
-            .iter()
-            .enumerate()
-            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .map(|(index, _)| index);
+            self.set_secondary_bounds(
+                axis_lo + t_lo * (axis_up - axis_lo),
+                axis_lo + t_up * (axis_up - axis_lo),
+            );
+        }
+
+        self.apply_bounds(lo, up);
+    }
 
-        let tick_x = psx[min_x_index.unwrap()];
+    /// Set the range of the secondary (right-hand) y axis, creating the axis if needed. The tick
+    /// period of the axis is automatically adjusted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo >= up`.
+    pub fn set_secondary_bounds(&mut self, lo: f32, up: f32) {
+        if lo >= up {
+            panic!("when using plot.set_secondary_bounds(), lo must be strictly less than up");
+        }
 
-        let order_y = exact_tick.y.log10().floor();
-        let mag_y = 10_f32.powf(order_y);
+        let tick_period = nice_tick_period(up - lo);
 
-        let p1y = mag_y * 1.0;
-        let p2y = mag_y * 2.0;
-        let p5y = mag_y * 5.0;
+        match self.secondary_axis.as_mut() {
+            Some(axis) => {
+                axis.lo = lo;
+                axis.up = up;
+                axis.tick_period = tick_period;
+            }
+            None => {
+                self.secondary_axis = Some(SecondaryAxis {
+                    lo,
+                    up,
+                    tick_period,
+                    formatter: None,
+                    label_color: self.tick_label_color,
+                });
+            }
+        }
+    }
 
-        let psy = [p1y, p2y, p5y];
+    /// Sets the bounds of the y axis the series is bound to. The x range always goes to the main bounds.
+    /// The main bounds are left alone if they were set with [`Plot::set_bounds`].
+    fn fit_bounds(&mut self, lo: Vec2, up: Vec2, y_axis: YAxis) {
+        match y_axis {
+            YAxis::Left if self.bounds_set_by_user => {}
+            YAxis::Left => self.apply_bounds(lo, up),
+            YAxis::Right => {
+                if !self.bounds_set_by_user {
+                    self.apply_bounds(Vec2::new(lo.x, self.bounds.lo.y), Vec2::new(up.x, self.bounds.up.y));
+                }
+                self.set_secondary_bounds(lo.y, up.y);
+            }
+        }
+    }
 
-        let vy = vec![
-            (p1y - exact_tick.y).abs(),
-            (p2y - exact_tick.y).abs(),
-            (p5y - exact_tick.y).abs(),
-        ];
+    /// Pins a target at `position`, in plot coordinates. The differences in x and y, the slope and the
+    /// distance between consecutive pinned targets are shown between them.
+    pub fn pin_target(&mut self, position: Vec2) {
+        self.pinned_targets.push(position);
+    }
 
-        let min_y_index = vy
-            .iter()
-            .enumerate()
-            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
-            .map(|(index, _)| index);
+    /// Removes the pinned target at `index`, in the order the targets were pinned.
+    pub fn unpin_target(&mut self, index: usize) -> Option<Vec2> {
+        if index < self.pinned_targets.len() {
+            Some(self.pinned_targets.remove(index))
+        } else {
+            None
+        }
+    }
 
-        let tick_y = psy[min_y_index.unwrap()];
+    pub fn clear_pinned_targets(&mut self) {
+        self.pinned_targets.clear();
+    }
 
-        self.tick_period = Vec2::new(tick_x, tick_y);
+    /// Positions of the pinned targets in plot coordinates, in the order they were pinned.
+    pub fn pinned_targets(&self) -> &[Vec2] {
+        &self.pinned_targets
+    }
 
-        self.compute_zeros();
+    /// Convert a point in the coordinates of the given y axis to a point in world coordinates
+    /// modulo the canvas position.
+    pub fn to_local_axis(&self, v: Vec2, y_axis: YAxis) -> Vec2 {
+        match (y_axis, self.secondary_axis.as_ref()) {
+            (YAxis::Right, Some(axis)) => self.to_local(Vec2::new(v.x, axis.to_primary(v.y, &self.bounds))),
+            _ => self.to_local(v),
+        }
     }
 
     pub(crate) fn compute_zeros(&mut self) {
@@ -993,10 +2643,228 @@ impl Plot {
         self.zero_world + v * self.canvas_size / (self.bounds.up - self.bounds.lo) / (1.0 + self.outer_border.x)
     }
 
-    /// Convert a point in world coordinates to a point in the graph coordinates.
+    /// Convert a point in world coordinates to a point in the graph coordinates. The world coordinates are
+    /// the ones of the camera, with y pointing up, as returned by `Camera::viewport_to_world_2d`.
+    ///
+    /// Breaking change: `v` used to be expected with its y coordinate flipped (pointing down). Code that
+    /// flipped y before calling this method should now pass the world position as is.
     pub fn world_to_plot(&self, v: Vec2) -> Vec2 {
-        let v = Vec2::new(v.x, -v.y);
         (v - self.zero_world - self.canvas_position) * (self.bounds.up - self.bounds.lo) / self.canvas_size
             * (1.0 + self.outer_border)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plot_at(canvas_position: Vec2) -> Plot {
+        let mut plot = Plot::default();
+        plot.canvas_position = canvas_position;
+        plot.set_bounds(Vec2::new(0.0, 0.0), Vec2::new(10.0, 20.0));
+        plot.compute_zeros();
+        plot
+    }
+
+    #[test]
+    fn world_to_plot_maps_the_center_of_the_canvas_to_the_center_of_the_bounds() {
+        let plot = plot_at(Vec2::new(100.0, 50.0));
+
+        let center = plot.world_to_plot(Vec2::new(100.0, 50.0));
+        assert!(center.distance(Vec2::new(5.0, 10.0)) < 1e-3, "{:?}", center);
+    }
+
+    #[test]
+    fn world_to_plot_has_y_pointing_up() {
+        let plot = plot_at(Vec2::new(100.0, 50.0));
+        let inner_half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+
+        let top_right = plot.world_to_plot(Vec2::new(100.0, 50.0) + inner_half_size);
+        assert!(top_right.distance(Vec2::new(10.0, 20.0)) < 1e-3, "{:?}", top_right);
+
+        let bottom_left = plot.world_to_plot(Vec2::new(100.0, 50.0) - inner_half_size);
+        assert!(bottom_left.distance(Vec2::new(0.0, 0.0)) < 1e-3, "{:?}", bottom_left);
+    }
+    fn steps(step: Option<StepMode>) -> Vec<Vec2> {
+        let segment = SegmentData {
+            data: vec![Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0), Vec2::new(4.0, 3.0)],
+            step,
+            ..Default::default()
+        };
+        segment.points()
+    }
+
+    #[test]
+    fn segment_points_without_step_are_the_data() {
+        assert_eq!(steps(None), vec![Vec2::new(0.0, 0.0), Vec2::new(2.0, 1.0), Vec2::new(4.0, 3.0)]);
+    }
+
+    #[test]
+    fn pre_step_jumps_at_the_previous_point() {
+        let expected = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(0.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(2.0, 3.0),
+            Vec2::new(4.0, 3.0),
+        ];
+        assert_eq!(steps(Some(StepMode::Pre)), expected);
+    }
+
+    #[test]
+    fn post_step_holds_until_the_next_point() {
+        let expected = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(4.0, 1.0),
+            Vec2::new(4.0, 3.0),
+        ];
+        assert_eq!(steps(Some(StepMode::Post)), expected);
+    }
+
+    #[test]
+    fn mid_step_jumps_halfway() {
+        let expected = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(3.0, 1.0),
+            Vec2::new(3.0, 3.0),
+            Vec2::new(4.0, 3.0),
+        ];
+        assert_eq!(steps(Some(StepMode::Mid)), expected);
+    }
+
+    #[test]
+    fn step_of_a_single_point_is_the_point() {
+        let segment = SegmentData {
+            data: vec![Vec2::new(1.0, 2.0)],
+            step: Some(StepMode::Mid),
+            ..Default::default()
+        };
+        assert_eq!(segment.points(), vec![Vec2::new(1.0, 2.0)]);
+    }
+
+    fn areas(layers: Vec<Vec<f32>>, normalize: bool) -> StackedAreaData {
+        StackedAreaData {
+            xs: vec![0.0, 1.0, 2.0],
+            layers,
+            normalize,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stacked_layers_are_cumulative_sums() {
+        let data = areas(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]], false);
+        assert_eq!(data.stacked(), vec![vec![1.0, 2.0, 3.0], vec![5.0, 7.0, 9.0]]);
+    }
+
+    #[test]
+    fn stacked_layers_count_negative_and_nan_values_as_zero() {
+        let data = areas(vec![vec![1.0, -2.0, f32::NAN], vec![1.0, 1.0, 1.0]], false);
+        assert_eq!(data.stacked(), vec![vec![1.0, 0.0, 0.0], vec![2.0, 1.0, 1.0]]);
+    }
+
+    #[test]
+    fn normalized_stack_sums_up_to_a_hundred() {
+        let data = areas(vec![vec![1.0, 3.0, 0.0], vec![3.0, 1.0, 0.0]], true);
+        // an x value where every layer is zero stays at zero
+        assert_eq!(data.stacked(), vec![vec![25.0, 75.0, 0.0], vec![100.0, 100.0, 0.0]]);
+    }
+
+    #[test]
+    fn stacked_layers_stop_at_the_shortest_series() {
+        let data = areas(vec![vec![1.0, 2.0], vec![1.0, 1.0, 1.0, 1.0]], false);
+        assert_eq!(data.stacked(), vec![vec![1.0, 2.0], vec![2.0, 3.0]]);
+    }
+
+    fn assert_angles(angles: &[(f32, f32)], expected: &[(f32, f32)]) {
+        assert_eq!(angles.len(), expected.len());
+        for (angle, expected) in angles.iter().zip(expected.iter()) {
+            assert!((angle.0 - expected.0).abs() < 1e-5 && (angle.1 - expected.1).abs() < 1e-5, "{:?}", angles);
+        }
+    }
+
+    #[test]
+    fn pie_slices_go_clockwise_from_the_start_angle() {
+        use std::f32::consts::FRAC_PI_2;
+
+        let pie = PieData {
+            values: vec![1.0, 1.0, 2.0],
+            ..Default::default()
+        };
+        let expected = [(0.0, FRAC_PI_2), (-FRAC_PI_2, 0.0), (-3.0 * FRAC_PI_2, -FRAC_PI_2)];
+        // the last slice closes the whole turn back at the start angle
+        assert_angles(&pie.angles(), &expected);
+    }
+
+    #[test]
+    fn negative_pie_values_get_an_empty_slice() {
+        let pie = PieData {
+            values: vec![-1.0, 1.0],
+            start_angle: 0.0,
+            ..Default::default()
+        };
+        assert_angles(&pie.angles(), &[(0.0, 0.0), (-std::f32::consts::TAU, 0.0)]);
+    }
+
+    #[test]
+    fn pie_without_positive_values_has_only_empty_slices() {
+        let pie = PieData {
+            values: vec![0.0, -2.0],
+            start_angle: 1.0,
+            ..Default::default()
+        };
+        assert_angles(&pie.angles(), &[(1.0, 1.0), (1.0, 1.0)]);
+    }
+
+    fn radar(values: Vec<f32>, max_value: Option<f32>) -> RadarData {
+        RadarData {
+            axes: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            series: vec![RadarSeries {
+                name: "series".to_string(),
+                values,
+                color: Color::WHITE,
+            }],
+            max_value,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn radar_outer_value_is_the_largest_finite_value() {
+        assert_eq!(radar(vec![1.0, f32::NAN, 3.0], None).outer_value(), 3.0);
+        assert_eq!(radar(vec![1.0, f32::INFINITY, 3.0], None).outer_value(), 3.0);
+    }
+
+    #[test]
+    fn radar_outer_value_prefers_the_max_value() {
+        assert_eq!(radar(vec![1.0, 2.0, 3.0], Some(10.0)).outer_value(), 10.0);
+    }
+
+    #[test]
+    fn radar_outer_value_is_always_positive() {
+        assert_eq!(radar(vec![0.0, -1.0, f32::NAN], None).outer_value(), 1.0);
+        assert_eq!(radar(vec![1.0, 2.0, 3.0], Some(0.0)).outer_value(), 3.0);
+        assert_eq!(radar(vec![1.0, 2.0, 3.0], Some(f32::NAN)).outer_value(), 3.0);
+    }
+
+    #[test]
+    fn radaropt_draws_non_finite_values_at_the_center() {
+        let mut plot = Plot::default();
+        plot.radaropt(vec!["a", "b", "c"], vec![("series", vec![1.0, f32::NAN, f32::NEG_INFINITY])], vec![]);
+        assert_eq!(plot.data.radar_groups[0].series[0].values, vec![1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn tick_period_is_one_two_or_five_times_a_power_of_ten() {
+        assert_eq!(nice_tick_period(10.0), 1.0);
+        assert_eq!(nice_tick_period(20.0), 2.0);
+        assert_eq!(nice_tick_period(45.0), 5.0);
+        assert_eq!(nice_tick_period(800.0), 50.0);
+        assert!((nice_tick_period(0.02) - 0.002).abs() < 1e-9);
+    }
+}
@@ -159,3 +159,87 @@ impl Plotable for Vec<f64> {
         }
     }
 }
+
+/// One period of a financial (OHLC) series, displayed as a candle by [`crate::Plot::candlestick`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Candle {
+    pub time: f32,
+    pub open: f32,
+    pub high: f32,
+    pub low: f32,
+    pub close: f32,
+    /// Traded volume, shown in the strip below the candles if present
+    pub volume: Option<f32>,
+}
+
+/// Equivalent of [`Plotable`] for financial series: `(time, open, high, low, close)` tuples,
+/// optionally followed by the volume.
+pub trait PlotableOhlc {
+    fn to_candles(&self) -> Vec<Candle>;
+}
+
+impl PlotableOhlc for Vec<Candle> {
+    fn to_candles(&self) -> Vec<Candle> {
+        self.clone()
+    }
+}
+
+impl PlotableOhlc for Vec<(f32, f32, f32, f32, f32)> {
+    fn to_candles(&self) -> Vec<Candle> {
+        self.iter()
+            .map(|(time, open, high, low, close)| Candle {
+                time: *time,
+                open: *open,
+                high: *high,
+                low: *low,
+                close: *close,
+                volume: None,
+            })
+            .collect()
+    }
+}
+
+impl PlotableOhlc for Vec<(f64, f64, f64, f64, f64)> {
+    fn to_candles(&self) -> Vec<Candle> {
+        self.iter()
+            .map(|(time, open, high, low, close)| Candle {
+                time: *time as f32,
+                open: *open as f32,
+                high: *high as f32,
+                low: *low as f32,
+                close: *close as f32,
+                volume: None,
+            })
+            .collect()
+    }
+}
+
+impl PlotableOhlc for Vec<(f32, f32, f32, f32, f32, f32)> {
+    fn to_candles(&self) -> Vec<Candle> {
+        self.iter()
+            .map(|(time, open, high, low, close, volume)| Candle {
+                time: *time,
+                open: *open,
+                high: *high,
+                low: *low,
+                close: *close,
+                volume: Some(*volume),
+            })
+            .collect()
+    }
+}
+
+impl PlotableOhlc for Vec<(f64, f64, f64, f64, f64, f64)> {
+    fn to_candles(&self) -> Vec<Candle> {
+        self.iter()
+            .map(|(time, open, high, low, close, volume)| Candle {
+                time: *time as f32,
+                open: *open as f32,
+                high: *high as f32,
+                low: *low as f32,
+                close: *close as f32,
+                volume: Some(*volume as f32),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+        view::RenderLayers,
+    },
+};
+
+use super::interaction::*;
+use super::plot::*;
+
+/// Offscreen target of a plot: the plot is drawn into `image` by its own camera instead of appearing
+/// in the window, so that the image can be used as the texture of a 3d material or of a UI image.
+/// See [`Plot::render_to_texture`].
+#[derive(Debug, Clone)]
+pub struct PlotTexture {
+    /// Image the plot is rendered to. It is resized along with the canvas
+    pub image: Handle<Image>,
+
+    /// Render layer of the plot and of its camera. Layer 0 is the one of the other cameras, so it
+    /// should not be used
+    pub layer: usize,
+
+    /// Color of the image outside of the canvas, transparent by default
+    pub clear_color: Color,
+}
+
+/// Camera rendering the plot with the given id to its [`PlotTexture`].
+#[derive(Component)]
+pub(crate) struct PlotTextureCamera(pub PlotId);
+
+/// Size in pixels of the image for a canvas of the given size.
+fn texture_size(canvas_size: Vec2) -> Extent3d {
+    Extent3d {
+        width: canvas_size.x.ceil().max(1.0) as u32,
+        height: canvas_size.y.ceil().max(1.0) as u32,
+        depth_or_array_layers: 1,
+    }
+}
+
+impl Plot {
+    /// Renders the plot offscreen on the given render layer, and returns the image it is drawn into.
+    /// The image has the size of the canvas in pixels and can be used as a texture on 3d meshes:
+    ///
+    /// ```ignore
+    /// let image = plot.render_to_texture(&mut images, 1);
+    /// let material = materials.add(StandardMaterial::from(image));
+    /// ```
+    ///
+    /// The image is in the sRGB format expected of color textures, and the camera of the plot renders it
+    /// before the cameras of the scene, so that a material shows the plot of the current frame. See
+    /// examples/plot_texture_3d.rs, built with the `pbr` feature.
+    ///
+    /// The plot does not react to the mouse anymore, since the cursor cannot reach it: its
+    /// [`interaction`](Plot::interaction) is locked and its tooltips are hidden.
+    pub fn render_to_texture(&mut self, images: &mut Assets<Image>, layer: usize) -> Handle<Image> {
+        let mut image = Image::new_fill(
+            texture_size(self.canvas_size),
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::RENDER_ATTACHMENT;
+
+        let handle = images.add(image);
+
+        self.render_target = Some(PlotTexture {
+            image: handle.clone(),
+            layer,
+            clear_color: Color::NONE,
+        });
+        self.interaction = PlotInteraction::locked();
+        self.show_tooltips = false;
+
+        handle
+    }
+}
+
+/// Spawns a camera for each plot rendered to a texture, keeps it centered on the canvas and keeps the
+/// image the size of the canvas.
+pub(crate) fn update_texture_cameras(
+    mut commands: Commands,
+    plots: Res<PlotMap>,
+    mut images: ResMut<Assets<Image>>,
+    mut cameras: Query<(Entity, &PlotTextureCamera, &mut Transform, &mut Camera)>,
+) {
+    for (camera_entity, texture_camera, mut transform, mut camera) in cameras.iter_mut() {
+        // the camera goes away with its plot or with the texture
+        let Some((plot, texture)) = plots
+            .get(&texture_camera.0)
+            .and_then(|plot| plot.render_target.as_ref().map(|texture| (plot, texture)))
+        else {
+            commands.entity(camera_entity).despawn();
+            continue;
+        };
+
+        let translation = plot.canvas_position.extend(0.0);
+        if transform.translation != translation {
+            transform.translation = translation;
+        }
+
+        // the image or the clear color may be replaced after the camera is spawned
+        if !matches!(&camera.target, RenderTarget::Image(target) if *target == texture.image) {
+            camera.target = RenderTarget::Image(texture.image.clone());
+        }
+
+        if !matches!(camera.clear_color, ClearColorConfig::Custom(color) if color == texture.clear_color)
+        {
+            camera.clear_color = ClearColorConfig::Custom(texture.clear_color);
+        }
+
+        if let Some(image) = images.get(&texture.image) {
+            let size = texture_size(plot.canvas_size);
+            if image.texture_descriptor.size != size {
+                if let Some(image) = images.get_mut(&texture.image) {
+                    image.resize(size);
+                }
+            }
+        }
+    }
+
+    for plot in plots.plots.values() {
+        let Some(texture) = plot.render_target.as_ref() else {
+            continue;
+        };
+
+        if cameras
+            .iter()
+            .any(|(_, texture_camera, _, _)| texture_camera.0 == plot.id)
+        {
+            continue;
+        }
+
+        commands.spawn((
+            Camera2d,
+            Camera {
+                target: RenderTarget::Image(texture.image.clone()),
+                // rendered before the cameras looking at the image
+                order: -1,
+                clear_color: ClearColorConfig::Custom(texture.clear_color),
+                ..default()
+            },
+            Transform::from_translation(plot.canvas_position.extend(0.0)),
+            RenderLayers::layer(texture.layer),
+            PlotTextureCamera(plot.id),
+        ));
+    }
+}
+
+/// Puts every entity of a plot rendered to a texture, children included, on the render layer of its camera.
+pub(crate) fn propagate_texture_layers(
+    mut commands: Commands,
+    plots: Res<PlotMap>,
+    roots: Query<(Entity, &PlotIdComponent), Without<PlotTextureCamera>>,
+    children: Query<&Children>,
+    layers: Query<&RenderLayers>,
+) {
+    for (root, plot_id_comp) in roots.iter() {
+        let Some(texture) = plots
+            .get(&plot_id_comp.0)
+            .and_then(|plot| plot.render_target.as_ref())
+        else {
+            continue;
+        };
+
+        let layer = RenderLayers::layer(texture.layer);
+
+        for entity in std::iter::once(root).chain(children.iter_descendants(root)) {
+            if layers.get(entity).ok() != Some(&layer) {
+                commands.entity(entity).insert(layer.clone());
+            }
+        }
+    }
+}
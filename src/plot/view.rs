@@ -0,0 +1,425 @@
+use bevy::prelude::*;
+
+use super::interaction::*;
+use super::plot::*;
+use crate::canvas::*;
+use crate::inputs::*;
+
+/// Maximum number of views kept in the undo history of a plot.
+const MAX_HISTORY: usize = 100;
+
+/// Wheel steps closer than this many seconds are undone together.
+const ZOOM_COALESCE_TIME: f32 = 0.5;
+
+/// Change of view requested with a [`PlotViewEvent`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ViewAction {
+    /// Goes back to the view before the last pan or zoom
+    Undo,
+    /// Cancels the last `Undo`
+    Redo,
+    /// Restores the range the plot had when it was spawned
+    Home,
+    /// Fits the axes to all the series of the plot, with margins
+    Fit,
+    /// Resumes the [`Autoscale`](super::Autoscale) policy of the plot after a pan or a zoom
+    Resume,
+}
+
+/// Send this event to change the view of a plot from code. The same actions are bound to keys
+/// (see [`PlotInputMap`]).
+#[derive(Event, Debug, Clone)]
+pub struct PlotViewEvent {
+    pub plot_id: PlotId,
+    pub action: ViewAction,
+}
+
+/// Range of the axes of a plot.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ViewState {
+    bounds: PlotCanvasBounds,
+    secondary: Option<(f32, f32)>,
+}
+
+/// Previous and undone views of a plot.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ViewHistory {
+    undo: Vec<ViewState>,
+    redo: Vec<ViewState>,
+
+    /// View when the plot was spawned
+    pub home: Option<ViewState>,
+
+    /// Time of the last wheel step that was recorded, if it was the last recorded change
+    last_zoom_time: Option<f32>,
+}
+
+/// Lower and upper corners of the range of `points`, or None if there is no finite point.
+pub(crate) fn range(points: impl Iterator<Item = Vec2>) -> Option<(Vec2, Vec2)> {
+    let (lo, up) = points
+        .filter(|p| p.is_finite())
+        .fold((Vec2::INFINITY, Vec2::NEG_INFINITY), |(lo, up), p| (lo.min(p), up.max(p)));
+
+    if lo.x <= up.x {
+        Some((lo, up))
+    } else {
+        None
+    }
+}
+
+/// Widens the range by 5% on each side, or by a fixed amount if the range is empty.
+pub(crate) fn with_margins(lo: f32, up: f32) -> (f32, f32) {
+    let margin = if up > lo {
+        (up - lo) * 0.05
+    } else {
+        (lo.abs() * 0.1).max(0.5)
+    };
+    (lo - margin, up + margin)
+}
+
+impl Plot {
+    pub(crate) fn view_state(&self) -> ViewState {
+        ViewState {
+            bounds: self.bounds.clone(),
+            secondary: self.secondary_axis.as_ref().map(|axis| (axis.lo, axis.up)),
+        }
+    }
+
+    fn restore_view(&mut self, view: ViewState) {
+        if let Some((lo, up)) = view.secondary {
+            self.set_secondary_bounds(lo, up);
+        }
+        self.apply_bounds(view.bounds.lo, view.bounds.up);
+    }
+
+    /// Records the current view before it is changed by a pan or a zoom.
+    pub(crate) fn push_view(&mut self) {
+        let view = self.view_state();
+        self.push_history(view);
+    }
+
+    /// Records `previous` once a change of view is over, unless the view did not actually change, as
+    /// for a click without dragging.
+    pub(crate) fn record_view(&mut self, previous: ViewState) {
+        if previous != self.view_state() {
+            self.push_history(previous);
+        }
+    }
+
+    fn push_history(&mut self, view: ViewState) {
+        let history = &mut self.view_history;
+
+        history.undo.push(view);
+        if history.undo.len() > MAX_HISTORY {
+            history.undo.remove(0);
+        }
+        history.redo.clear();
+        history.last_zoom_time = None;
+    }
+
+    /// Same as [`Plot::push_view`], except that consecutive wheel steps are recorded as a single change.
+    pub(crate) fn push_zoom_view(&mut self, time: f32) {
+        let coalesce = self
+            .view_history
+            .last_zoom_time
+            .is_some_and(|last| time - last < ZOOM_COALESCE_TIME);
+
+        if !coalesce {
+            self.push_view();
+        }
+        self.view_history.last_zoom_time = Some(time);
+    }
+
+    /// Points of the series bound to the given y axis, in plot coordinates. Candles contribute their
+    /// low and high, stems and stacked areas their baseline.
+    pub(crate) fn series_points(&self, y_axis: YAxis) -> Vec<Vec2> {
+        let data = &self.data;
+        let mut points: Vec<Vec2> = vec![];
+
+        for segments in data.segment_groups.iter().filter(|s| s.y_axis == y_axis) {
+            points.extend(segments.data.iter());
+        }
+        for markers in data.marker_groups.iter().filter(|s| s.y_axis == y_axis) {
+            points.extend(markers.data.iter());
+        }
+        for stems in data.stem_groups.iter().filter(|s| s.y_axis == y_axis) {
+            points.extend(stems.data.iter());
+            points.extend(stems.data.iter().map(|p| Vec2::new(p.x, stems.baseline)));
+        }
+        for candles in data.candlestick_groups.iter().filter(|s| s.y_axis == y_axis) {
+            points.extend(candles.data.iter().map(|c| Vec2::new(c.time, c.low)));
+            points.extend(candles.data.iter().map(|c| Vec2::new(c.time, c.high)));
+        }
+        for areas in data.stacked_area_groups.iter().filter(|s| s.y_axis == y_axis) {
+            for layer in areas.stacked().iter() {
+                points.extend(areas.xs.iter().zip(layer.iter()).map(|(x, y)| Vec2::new(*x, *y)));
+            }
+            points.extend(areas.xs.iter().map(|x| Vec2::new(*x, 0.0)));
+        }
+
+        if y_axis == YAxis::Left {
+            for quiver in data.quiver_groups.iter() {
+                if let QuiverField::Data(arrows) = &quiver.field {
+                    points.extend(arrows.iter().map(|(position, _)| *position));
+                }
+            }
+            for contour in data.contour_groups.iter() {
+                if let ContourField::Grid { lo, up, .. } = &contour.field {
+                    points.extend([*lo, *up]);
+                }
+            }
+        }
+
+        points
+    }
+
+    /// Range of the series bound to the given y axis, in plot coordinates.
+    fn data_range(&self, y_axis: YAxis) -> Option<(Vec2, Vec2)> {
+        range(self.series_points(y_axis).into_iter())
+    }
+
+    /// Fits the axes to the data of all the series, with margins. Nothing happens if there is no data.
+    pub(crate) fn fit_to_data(&mut self) {
+        let left = self.data_range(YAxis::Left);
+        let right = self.secondary_axis.as_ref().and(self.data_range(YAxis::Right));
+
+        let Some((lo_x, up_x)) = [left, right]
+            .iter()
+            .flatten()
+            .map(|(lo, up)| (lo.x, up.x))
+            .reduce(|(lo_a, up_a), (lo_b, up_b)| (lo_a.min(lo_b), up_a.max(up_b)))
+        else {
+            return;
+        };
+
+        let (lo_x, up_x) = with_margins(lo_x, up_x);
+        let (lo_y, up_y) = match left {
+            Some((lo, up)) => with_margins(lo.y, up.y),
+            None => (self.bounds.lo.y, self.bounds.up.y),
+        };
+
+        if let Some((lo, up)) = right {
+            let (lo, up) = with_margins(lo.y, up.y);
+            self.set_secondary_bounds(lo, up);
+        }
+
+        self.apply_bounds(Vec2::new(lo_x, lo_y), Vec2::new(up_x, up_y));
+    }
+
+    /// Applies a [`ViewAction`], recording the current view when needed. All the actions but `Resume`
+    /// pause the autoscale policy.
+    pub(crate) fn apply_view_action(&mut self, action: ViewAction) {
+        self.autoscale.paused = action != ViewAction::Resume;
+
+        match action {
+            ViewAction::Undo => {
+                // views identical to the current one, for instance a wheel step at the zoom limit, are skipped
+                let current = self.view_state();
+                while self.view_history.undo.last() == Some(&current) {
+                    self.view_history.undo.pop();
+                }
+
+                if let Some(view) = self.view_history.undo.pop() {
+                    self.view_history.redo.push(current);
+                    self.view_history.last_zoom_time = None;
+                    self.restore_view(view);
+                }
+            }
+            ViewAction::Redo => {
+                if let Some(view) = self.view_history.redo.pop() {
+                    let current = self.view_state();
+                    self.view_history.undo.push(current);
+                    self.view_history.last_zoom_time = None;
+                    self.restore_view(view);
+                }
+            }
+            ViewAction::Home => {
+                if let Some(view) = self.view_history.home.clone() {
+                    let previous = self.view_state();
+                    self.restore_view(view);
+                    self.record_view(previous);
+                }
+            }
+            ViewAction::Fit => {
+                let previous = self.view_state();
+                self.fit_to_data();
+                self.record_view(previous);
+            }
+            ViewAction::Resume => {}
+        }
+    }
+}
+
+/// Sends a [`PlotViewEvent`] for the plot under the cursor when one of the view keys is pressed.
+pub(crate) fn view_keys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input_map: Res<PlotInputMap>,
+    cursor: Res<Cursor>,
+    plots: Res<PlotMap>,
+    canvas_query: Query<(&CanvasParams, &PlotIdComponent)>,
+    mut view_event: EventWriter<PlotViewEvent>,
+) {
+    let bindings = [
+        (input_map.undo_key, ViewAction::Undo),
+        (input_map.redo_key, ViewAction::Redo),
+        (input_map.home_key, ViewAction::Home),
+        (input_map.fit_key, ViewAction::Fit),
+        (input_map.resume_autoscale_key, ViewAction::Resume),
+    ];
+
+    for (graph_sprite, plot_id) in canvas_query.iter() {
+        let enabled = plots.get(&plot_id.0).is_some_and(|plot| plot.interaction.view_keys);
+        let hovered = cursor.hovered_plot == Some(plot_id.0);
+
+        if !enabled || !hovered || !graph_sprite.within_rect(cursor.position) {
+            continue;
+        }
+
+        for (key, action) in bindings.iter() {
+            if input_map.shortcut_just_pressed(&keyboard_input, *key) {
+                view_event.send(PlotViewEvent {
+                    plot_id: plot_id.0,
+                    action: *action,
+                });
+            }
+        }
+    }
+}
+
+pub(crate) fn apply_view_actions(
+    mut plots: ResMut<PlotMap>,
+    mut view_event: EventReader<PlotViewEvent>,
+    canvas_query: Query<(Entity, &PlotIdComponent, &MeshMaterial2d<CanvasMaterial>)>,
+    mut update_plot_labels_event: EventWriter<UpdatePlotLabelsEvent>,
+    mut update_target_labels_event: EventWriter<UpdateTargetLabelEvent>,
+    mut respawn_all_event: EventWriter<RespawnAllEvent>,
+    mut axes_changed_event: EventWriter<AxesChangedEvent>,
+) {
+    for event in view_event.read() {
+        let Some(plot) = plots.get_mut(&event.plot_id) else {
+            continue;
+        };
+
+        plot.apply_view_action(event.action);
+
+        for (canvas_entity, plot_id_comp, material_handle) in canvas_query.iter() {
+            if plot_id_comp.0 != event.plot_id {
+                continue;
+            }
+
+            update_plot_labels_event.send(UpdatePlotLabelsEvent {
+                plot_id: event.plot_id,
+                canvas_entity,
+            });
+
+            update_target_labels_event.send(UpdateTargetLabelEvent {
+                plot_id: event.plot_id,
+                canvas_entity,
+                canvas_material_handle: material_handle.clone(),
+            });
+        }
+
+        respawn_all_event.send(RespawnAllEvent { plot_id: event.plot_id });
+        axes_changed_event.send(AxesChangedEvent { plot_id: event.plot_id });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zoom_to(plot: &mut Plot, up: f32) {
+        plot.apply_bounds(Vec2::ZERO, Vec2::splat(up));
+    }
+
+    fn up(plot: &Plot) -> f32 {
+        plot.bounds.up.x
+    }
+
+    #[test]
+    fn undo_and_redo_walk_through_the_views() {
+        let mut plot = Plot::default();
+        zoom_to(&mut plot, 1.0);
+        plot.push_view();
+        zoom_to(&mut plot, 2.0);
+        plot.push_view();
+        zoom_to(&mut plot, 3.0);
+
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 2.0);
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 1.0);
+        // nothing left to undo
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 1.0);
+
+        plot.apply_view_action(ViewAction::Redo);
+        assert_eq!(up(&plot), 2.0);
+        plot.apply_view_action(ViewAction::Redo);
+        assert_eq!(up(&plot), 3.0);
+    }
+
+    #[test]
+    fn new_view_clears_the_redo_history() {
+        let mut plot = Plot::default();
+        zoom_to(&mut plot, 1.0);
+        plot.push_view();
+        zoom_to(&mut plot, 2.0);
+
+        plot.apply_view_action(ViewAction::Undo);
+        plot.push_view();
+        zoom_to(&mut plot, 5.0);
+
+        plot.apply_view_action(ViewAction::Redo);
+        assert_eq!(up(&plot), 5.0);
+    }
+
+    #[test]
+    fn unchanged_view_is_not_recorded() {
+        let mut plot = Plot::default();
+        zoom_to(&mut plot, 1.0);
+        plot.push_view();
+        zoom_to(&mut plot, 2.0);
+
+        // a click without dragging
+        let previous = plot.view_state();
+        plot.record_view(previous);
+
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 1.0);
+    }
+
+    #[test]
+    fn close_wheel_steps_are_undone_together() {
+        let mut plot = Plot::default();
+        zoom_to(&mut plot, 1.0);
+        plot.push_zoom_view(0.0);
+        zoom_to(&mut plot, 2.0);
+        plot.push_zoom_view(0.2);
+        zoom_to(&mut plot, 3.0);
+        plot.push_zoom_view(0.4);
+        zoom_to(&mut plot, 4.0);
+
+        // a pause longer than ZOOM_COALESCE_TIME starts a new change
+        plot.push_zoom_view(1.0);
+        zoom_to(&mut plot, 5.0);
+
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 4.0);
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 1.0);
+    }
+
+    #[test]
+    fn undo_skips_the_views_identical_to_the_current_one() {
+        let mut plot = Plot::default();
+        zoom_to(&mut plot, 1.0);
+        plot.push_view();
+        zoom_to(&mut plot, 2.0);
+        // a wheel step at the zoom limit records the view without changing it
+        plot.push_view();
+
+        plot.apply_view_action(ViewAction::Undo);
+        assert_eq!(up(&plot), 1.0);
+    }
+}
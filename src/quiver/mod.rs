@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes of the quiver plots, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct QuiverPart;
+
+/// Positions and vectors of the arrows, in plot coordinates, together with the distance between
+/// neighbouring arrows used for the automatic scaling.
+fn sample_field(quiver: &QuiverData, bounds: &PlotCanvasBounds) -> (Vec<(Vec2, Vec2)>, Vec2) {
+    match &quiver.field {
+        QuiverField::Data(data) => (data.clone(), (bounds.up - bounds.lo) / 10.0),
+
+        QuiverField::Function(f) => {
+            let n = quiver.num_points;
+            let cell = (bounds.up - bounds.lo) / n as f32;
+            let samples = (0..n)
+                .flat_map(|j| (0..n).map(move |i| (i, j)))
+                .map(|(i, j)| {
+                    let p = bounds.lo + (Vec2::new(i as f32, j as f32) + 0.5) * cell;
+                    (p, f(p.x, p.y))
+                })
+                .collect();
+
+            (samples, cell)
+        }
+    }
+}
+
+pub(crate) fn quiver_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<QuiverPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        for quiver in plot.data.quiver_groups.iter() {
+            let (arrows, cell) = sample_field(quiver, &plot.bounds);
+
+            let magnitudes = arrows
+                .iter()
+                .map(|(_, v)| v.length())
+                .filter(|m| m.is_finite())
+                .collect::<Vec<f32>>();
+            let min_mag = magnitudes.iter().copied().fold(f32::INFINITY, f32::min);
+            let max_mag = magnitudes.iter().copied().fold(0.0, f32::max);
+
+            if max_mag <= 0.0 {
+                continue;
+            }
+
+            // the axes can have different units, so the automatic scale is computed in pixels
+            let pixels_per_unit = plot.canvas_size / (1.0 + plot.outer_border) / (plot.bounds.up - plot.bounds.lo);
+            let max_pixels = arrows
+                .iter()
+                .map(|(_, v)| (*v * pixels_per_unit).length())
+                .filter(|m| m.is_finite())
+                .fold(0.0, f32::max);
+            let scale = quiver
+                .scale
+                .unwrap_or(0.9 * (cell * pixels_per_unit).min_element() / max_pixels.max(f32::EPSILON));
+
+            let mut builder = ShapeMeshBuilder::default();
+
+            for (position, vector) in arrows.iter() {
+                if !vector.is_finite() {
+                    continue;
+                }
+
+                let color = if quiver.color_by_magnitude {
+                    let t = (vector.length() - min_mag) / (max_mag - min_mag).max(f32::EPSILON);
+                    viridis(t)
+                } else {
+                    quiver.color
+                };
+
+                builder.arrow(
+                    plot.to_local(*position),
+                    plot.to_local(*position + *vector * scale),
+                    quiver.size * 2.0,
+                    color,
+                );
+            }
+
+            spawn_shape_mesh(
+                &mut commands,
+                &mut meshes,
+                &mut shape_materials,
+                plot,
+                builder,
+                1.08,
+                QuiverPart,
+            );
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+
+use crate::canvas::*;
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes and labels of the radar charts, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct RadarPart;
+
+/// Unit vector of the `k`-th of `num_axes` axes, going clockwise from the top.
+fn axis_direction(k: usize, num_axes: usize) -> Vec2 {
+    Vec2::from_angle(std::f32::consts::FRAC_PI_2 - k as f32 * std::f32::consts::TAU / num_axes as f32)
+}
+
+pub(crate) fn radar_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    maybe_font: Res<TickLabelFont>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<RadarPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        let inner_size = plot.canvas_size / (1.0 + plot.outer_border);
+        let font_size = 16.0;
+        let guide_color = plot.tick_label_color.with_alpha(0.4);
+
+        for radar in plot.data.radar_groups.iter() {
+            let num_axes = radar.axes.len();
+            if num_axes < 3 {
+                continue;
+            }
+
+            let radius = 0.38 * inner_size.x.min(inner_size.y);
+            let outer_value = radar.outer_value();
+            let directions = (0..num_axes)
+                .map(|k| axis_direction(k, num_axes))
+                .collect::<Vec<Vec2>>();
+
+            let mut builder = ShapeMeshBuilder::default();
+
+            // rings and radial axes
+            for ring in 1..=radar.num_rings {
+                let ring_radius = radius * ring as f32 / radar.num_rings as f32;
+                let mut points = directions.iter().map(|d| *d * ring_radius).collect::<Vec<Vec2>>();
+                points.push(points[0]);
+                builder.polyline(&points, 1.0, guide_color);
+            }
+            for direction in directions.iter() {
+                builder.line(Vec2::ZERO, *direction * radius, 1.0, guide_color);
+            }
+
+            // one filled polygon per series
+            for series in radar.series.iter() {
+                let points = directions
+                    .iter()
+                    .zip(series.values.iter().chain(std::iter::repeat(&0.0)))
+                    .map(|(direction, value)| {
+                        // clamp lets NaN through
+                        let t = value / outer_value;
+                        let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+                        *direction * radius * t
+                    })
+                    .collect::<Vec<Vec2>>();
+
+                for k in 0..num_axes {
+                    builder.triangle(
+                        Vec2::ZERO,
+                        points[k],
+                        points[(k + 1) % num_axes],
+                        series.color.with_alpha(0.35),
+                    );
+                }
+
+                let mut outline = points.clone();
+                outline.push(points[0]);
+                builder.polyline(&outline, radar.size * 2.0, series.color);
+                for point in points.iter() {
+                    builder.disk(*point, radar.size * 3.0, series.color);
+                }
+            }
+
+            // legend squares
+            let legend_top_right = inner_size / 2.0 - Vec2::splat(font_size);
+            if radar.show_legend {
+                for (k, series) in radar.series.iter().enumerate() {
+                    let center = legend_top_right - Vec2::new(0.0, k as f32 * font_size * 1.4);
+                    builder.rect(
+                        center - Vec2::splat(font_size * 0.35),
+                        center + Vec2::splat(font_size * 0.35),
+                        series.color,
+                    );
+                }
+            }
+
+            spawn_shape_mesh(
+                &mut commands,
+                &mut meshes,
+                &mut shape_materials,
+                plot,
+                builder,
+                1.01,
+                RadarPart,
+            );
+
+            let font = if let Some(font) = maybe_font.maybe_font.as_ref() {
+                font
+            } else {
+                continue;
+            };
+
+            // axis names
+            for (name, direction) in radar.axes.iter().zip(directions.iter()) {
+                let anchor = if direction.x > 0.1 {
+                    bevy::sprite::Anchor::CenterLeft
+                } else if direction.x < -0.1 {
+                    bevy::sprite::Anchor::CenterRight
+                } else if direction.y > 0.0 {
+                    bevy::sprite::Anchor::BottomCenter
+                } else {
+                    bevy::sprite::Anchor::TopCenter
+                };
+
+                spawn_shape_label(
+                    &mut commands,
+                    plot,
+                    name,
+                    *direction * (radius + 8.0),
+                    1.015,
+                    font_size,
+                    plot.tick_label_color,
+                    font,
+                    anchor,
+                    RadarPart,
+                );
+            }
+
+            // values of the rings along the first axis
+            for ring in 1..=radar.num_rings {
+                let value = outer_value * ring as f32 / radar.num_rings as f32;
+                let label = format_numeric_label(plot, value, value >= 1000.0 || (value < 0.01 && value > 0.0));
+
+                spawn_shape_label(
+                    &mut commands,
+                    plot,
+                    &label,
+                    directions[0] * radius * ring as f32 / radar.num_rings as f32 + Vec2::new(4.0, 0.0),
+                    1.015,
+                    font_size * 0.75,
+                    guide_color,
+                    font,
+                    bevy::sprite::Anchor::BottomLeft,
+                    RadarPart,
+                );
+            }
+
+            if radar.show_legend {
+                for (k, series) in radar.series.iter().enumerate() {
+                    let center = legend_top_right - Vec2::new(0.0, k as f32 * font_size * 1.4);
+
+                    spawn_shape_label(
+                        &mut commands,
+                        plot,
+                        &series.name,
+                        center - Vec2::new(font_size * 0.7, 0.0),
+                        1.015,
+                        font_size,
+                        plot.tick_label_color,
+                        font,
+                        bevy::sprite::Anchor::CenterRight,
+                        RadarPart,
+                    );
+                }
+            }
+        }
+    }
+}
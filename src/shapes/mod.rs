@@ -0,0 +1,6 @@
+pub mod respawn;
+pub mod shape_mesh;
+#[allow(unused_imports)]
+pub use respawn::*;
+#[allow(unused_imports)]
+pub use shape_mesh::*;
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+
+use std::collections::HashSet;
+
+use crate::plot::*;
+
+/// Shared first step of the setup systems of the shape-based series: reads the [`RespawnAllEvent`]s,
+/// despawns the parts marked with `T` of the plots to respawn, and returns these plots so that the
+/// caller only has to build their geometry again.
+pub(crate) fn respawned_plots<'a, T: Component>(
+    respawn_all_event: &mut EventReader<RespawnAllEvent>,
+    parts: &Query<(Entity, &PlotIdComponent), With<T>>,
+    commands: &mut Commands,
+    plots: &'a PlotMap,
+) -> impl Iterator<Item = &'a Plot> {
+    // several events for the same plot in a frame only respawn it once
+    let plot_ids = respawn_all_event
+        .read()
+        .map(|event| event.plot_id)
+        .collect::<HashSet<PlotId>>();
+
+    for (entity, plot_id_comp) in parts.iter() {
+        if plot_ids.contains(&plot_id_comp.0) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    plot_ids
+        .into_iter()
+        .filter_map(|plot_id| plots.get(&plot_id))
+}
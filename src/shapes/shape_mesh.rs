@@ -0,0 +1,278 @@
+use bevy::{
+    prelude::*,
+    reflect::TypePath,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+        render_resource::{AsBindGroup, ShaderRef},
+    },
+    sprite::{Material2d, Material2dPlugin},
+};
+
+use crate::plot::*;
+use crate::util::*;
+
+/// Renders the plain triangle meshes (with per-vertex colors) used by the series that do
+/// not need a dedicated shader: contours, filled areas, arrows, candles, etc.
+pub(crate) struct ShapeMesh2dPlugin;
+
+impl Plugin for ShapeMesh2dPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(Material2dPlugin::<ShapeMaterial>::default());
+    }
+}
+
+/// Material shared by all the shape meshes of a plot. Fragments falling outside the inner
+/// area of the canvas (the canvas minus its outer border) are discarded.
+#[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
+pub(crate) struct ShapeMaterial {
+    /// Center of the canvas in `World` coordinates
+    #[uniform(0)]
+    pub canvas_position: Vec2,
+
+    /// Size of the inner area of the canvas in pixels
+    #[uniform(0)]
+    pub inner_size: Vec2,
+}
+
+impl ShapeMaterial {
+    pub fn new(plot: &Plot) -> Self {
+        ShapeMaterial {
+            canvas_position: plot.canvas_position,
+            inner_size: plot.canvas_size / (1.0 + plot.outer_border),
+        }
+    }
+}
+
+impl Material2d for ShapeMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/shapes.wgsl".into()
+    }
+
+    fn alpha_mode(&self) -> bevy::sprite::AlphaMode2d {
+        bevy::sprite::AlphaMode2d::Blend
+    }
+}
+
+/// Accumulates colored triangles in the local reference frame of a canvas (see [`Plot::to_local`]).
+#[derive(Default)]
+pub(crate) struct ShapeMeshBuilder {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl ShapeMeshBuilder {
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    fn vertex(&mut self, position: Vec2, color: Vec4) -> u32 {
+        self.positions.push([position.x, position.y, 0.0]);
+        self.colors.push(color.to_array());
+        (self.positions.len() - 1) as u32
+    }
+
+    pub fn triangle(&mut self, a: Vec2, b: Vec2, c: Vec2, color: Color) {
+        self.polygon(&[a, b, c], color);
+    }
+
+    /// Fills a convex polygon using a triangle fan around its first vertex.
+    pub fn polygon(&mut self, points: &[Vec2], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+        let col = col_to_vec4(color);
+        let first = self.vertex(points[0], col);
+        let mut previous = self.vertex(points[1], col);
+        for point in points.iter().skip(2) {
+            let current = self.vertex(*point, col);
+            self.indices.extend_from_slice(&[first, previous, current]);
+            previous = current;
+        }
+    }
+
+    /// Axis-aligned rectangle between the corners `lo` and `up`.
+    pub fn rect(&mut self, lo: Vec2, up: Vec2, color: Color) {
+        self.polygon(&[lo, Vec2::new(up.x, lo.y), up, Vec2::new(lo.x, up.y)], color);
+    }
+
+    /// Segment from `a` to `b` with a `thickness` in pixels.
+    pub fn line(&mut self, a: Vec2, b: Vec2, thickness: f32, color: Color) {
+        let dir = (b - a).normalize_or_zero();
+        if dir == Vec2::ZERO {
+            return;
+        }
+        let normal = dir.perp() * thickness / 2.0;
+        self.polygon(&[a - normal, b - normal, b + normal, a + normal], color);
+    }
+
+    /// Consecutive segments joining `points`, with round joints.
+    pub fn polyline(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        for pair in points.windows(2) {
+            self.line(pair[0], pair[1], thickness, color);
+        }
+        if points.len() > 2 {
+            for point in &points[1..points.len() - 1] {
+                self.disk(*point, thickness / 2.0, color);
+            }
+        }
+    }
+
+    /// Dashes of length `dash` separated by `gap` along the segments joining `points`. The pattern
+    /// runs on across the vertices, so that short segments do not all start with a dash.
+    pub fn dashed_polyline(&mut self, points: &[Vec2], thickness: f32, dash: f32, gap: f32, color: Color) {
+        let period = dash + gap;
+        let mut phase = 0.0;
+
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let length = a.distance(b);
+            if length <= 0.0 {
+                continue;
+            }
+            let dir = (b - a) / length;
+
+            let mut s = 0.0;
+            while s < length {
+                let in_dash = phase < dash;
+                let remaining = if in_dash { dash - phase } else { period - phase };
+                let step = remaining.min(length - s);
+
+                if in_dash {
+                    self.line(a + dir * s, a + dir * (s + step), thickness, color);
+                }
+
+                s += step;
+                phase = (phase + step) % period;
+            }
+        }
+    }
+
+    /// Polyline drawn with the given `style`: nothing for [`LineStyle::None`], and dashes a few times
+    /// as long as the `thickness` for [`LineStyle::Dashed`].
+    pub fn styled_polyline(&mut self, points: &[Vec2], thickness: f32, style: &LineStyle, color: Color) {
+        match style {
+            LineStyle::None => {}
+            LineStyle::Solid => self.polyline(points, thickness, color),
+            LineStyle::Dashed => {
+                let unit = thickness.max(1.0);
+                self.dashed_polyline(points, thickness, 4.0 * unit, 3.0 * unit, color);
+            }
+        }
+    }
+
+    pub fn disk(&mut self, center: Vec2, radius: f32, color: Color) {
+        let num_sides = 16;
+        let points = (0..num_sides)
+            .map(|k| center + Vec2::from_angle(k as f32 * std::f32::consts::TAU / num_sides as f32) * radius)
+            .collect::<Vec<Vec2>>();
+        self.polygon(&points, color);
+    }
+
+    /// Filled marker of the given `style` centered at `center`. Styles without an outline
+    /// that can be filled (`Cross`, `X`, `Heart`, `Star`, `Moon`) fall back to a circle.
+    pub fn marker(&mut self, center: Vec2, radius: f32, style: &MarkerStyle, color: Color) {
+        match style {
+            MarkerStyle::None => {}
+            MarkerStyle::Square => self.rect(center - radius, center + radius, color),
+            MarkerStyle::Rhombus => self.polygon(
+                &[
+                    center + Vec2::new(0.0, -radius),
+                    center + Vec2::new(radius, 0.0),
+                    center + Vec2::new(0.0, radius),
+                    center + Vec2::new(-radius, 0.0),
+                ],
+                color,
+            ),
+            MarkerStyle::Triangle => self.triangle(
+                center + Vec2::new(-radius, -radius * 0.8),
+                center + Vec2::new(radius, -radius * 0.8),
+                center + Vec2::new(0.0, radius),
+                color,
+            ),
+            _ => self.disk(center, radius, color),
+        }
+    }
+
+    /// Arrow going from `start` to `end`, with a triangular head proportional to the `thickness`.
+    pub fn arrow(&mut self, start: Vec2, end: Vec2, thickness: f32, color: Color) {
+        let length = (end - start).length();
+        if length < 0.001 {
+            return;
+        }
+        let dir = (end - start) / length;
+        let head_length = (thickness * 5.0).min(length * 0.4);
+        let head_base = end - dir * head_length;
+        let head_half_width = dir.perp() * head_length * 0.5;
+
+        self.line(start, head_base, thickness, color);
+        self.triangle(end, head_base + head_half_width, head_base - head_half_width, color);
+    }
+
+    pub fn build(self) -> Mesh {
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, self.positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, self.colors)
+            .with_inserted_indices(Indices::U32(self.indices))
+    }
+}
+
+/// Spawns the triangles of `builder` on top of the canvas of `plot` at the `z` plane.
+/// The `bundle` is used to find the entity again when the plot is respawned.
+pub(crate) fn spawn_shape_mesh(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    shape_materials: &mut Assets<ShapeMaterial>,
+    plot: &Plot,
+    builder: ShapeMeshBuilder,
+    z: f32,
+    bundle: impl Bundle,
+) {
+    if builder.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Mesh2d(meshes.add(builder.build())),
+        MeshMaterial2d(shape_materials.add(ShapeMaterial::new(plot))),
+        Transform::from_translation(plot.canvas_position.extend(z)),
+        PlotIdComponent(plot.id),
+        bundle,
+    ));
+}
+
+/// Spawns a text label at `position`, given in the local reference frame of the canvas.
+/// Nothing is spawned if the label would fall outside the inner area of the canvas.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_shape_label(
+    commands: &mut Commands,
+    plot: &Plot,
+    text: &str,
+    position: Vec2,
+    z: f32,
+    font_size: f32,
+    color: Color,
+    font: &Handle<Font>,
+    anchor: bevy::sprite::Anchor,
+    bundle: impl Bundle,
+) {
+    let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+    if position.x.abs() > half_size.x || position.y.abs() > half_size.y {
+        return;
+    }
+
+    commands.spawn((
+        Text2d::new(text),
+        TextFont {
+            font: font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+        Transform::from_translation((plot.canvas_position + position).extend(z)),
+        anchor,
+        PlotIdComponent(plot.id),
+        bundle,
+    ));
+}
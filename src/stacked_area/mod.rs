@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes of the stacked area charts, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct StackedAreaPart;
+
+fn spawn_layers(builder: &mut ShapeMeshBuilder, plot: &Plot, area: &StackedAreaData) {
+    let stacked = area.stacked();
+
+    let mut lower = vec![0.0; stacked.first().map(|layer| layer.len()).unwrap_or(0)];
+
+    for (k, upper) in stacked.iter().enumerate() {
+        let color = area.colors.get(k).copied().unwrap_or(Color::srgb(0.5, 0.5, 0.5));
+
+        let top = area
+            .xs
+            .iter()
+            .zip(upper.iter())
+            .map(|(x, y)| plot.to_local_axis(Vec2::new(*x, *y), area.y_axis))
+            .collect::<Vec<Vec2>>();
+        let bottom = area
+            .xs
+            .iter()
+            .zip(lower.iter())
+            .map(|(x, y)| plot.to_local_axis(Vec2::new(*x, *y), area.y_axis))
+            .collect::<Vec<Vec2>>();
+
+        for i in 0..top.len().saturating_sub(1) {
+            builder.polygon(&[bottom[i], bottom[i + 1], top[i + 1], top[i]], color.with_alpha(0.8));
+        }
+
+        builder.styled_polyline(&top, area.size * 2.0, &area.line_style, color);
+
+        lower.clone_from(upper);
+    }
+}
+
+pub(crate) fn stacked_area_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<StackedAreaPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        for area in plot.data.stacked_area_groups.iter() {
+            let mut builder = ShapeMeshBuilder::default();
+            spawn_layers(&mut builder, plot, area);
+
+            spawn_shape_mesh(
+                &mut commands,
+                &mut meshes,
+                &mut shape_materials,
+                plot,
+                builder,
+                1.02,
+                StackedAreaPart,
+            );
+        }
+    }
+}
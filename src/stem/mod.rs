@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the meshes of the stem plots, so that they can be despawned on respawn.
+#[derive(Component)]
+pub(crate) struct StemPart;
+
+pub(crate) fn stem_setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    plots: Res<PlotMap>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+    query: Query<(Entity, &PlotIdComponent), With<StemPart>>,
+) {
+    for plot in respawned_plots(&mut respawn_all_event, &query, &mut commands, &plots) {
+        for stem in plot.data.stem_groups.iter() {
+            let mut builder = ShapeMeshBuilder::default();
+            let marker_radius = 6.0 * stem.marker.size;
+
+            for point in stem.data.iter() {
+                let top = plot.to_local_axis(*point, stem.y_axis);
+                let bottom = plot.to_local_axis(Vec2::new(point.x, stem.baseline), stem.y_axis);
+
+                builder.line(bottom, top, stem.size * 2.0, stem.color);
+                builder.marker(top, marker_radius, &stem.marker.marker_style, stem.marker.color);
+            }
+
+            spawn_shape_mesh(
+                &mut commands,
+                &mut meshes,
+                &mut shape_materials,
+                plot,
+                builder,
+                1.075,
+                StemPart,
+            );
+        }
+    }
+}
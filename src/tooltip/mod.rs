@@ -0,0 +1,461 @@
+use bevy::prelude::*;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::canvas::*;
+use crate::inputs::*;
+use crate::plot::*;
+use crate::shapes::*;
+
+/// Marks the highlight and the tooltip of the hovered data point, so that they can be despawned.
+#[derive(Component)]
+pub(crate) struct TooltipPart;
+
+/// Data point as seen on the canvas.
+#[derive(Debug, Clone)]
+pub(crate) struct IndexedPoint {
+    pub series: SeriesId,
+    pub index: usize,
+
+    /// Position in the local reference frame of the canvas (see [`Plot::to_local`])
+    pub local: Vec2,
+
+    /// Position in plot coordinates
+    pub position: Vec2,
+
+    pub color: Color,
+}
+
+/// Data points of a plot bucketed in square cells of the canvas, for fast nearest-point lookups.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PointGrid {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<IndexedPoint>>,
+}
+
+impl PointGrid {
+    fn new(cell_size: f32) -> Self {
+        PointGrid {
+            cell_size: cell_size.max(1.0),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, local: Vec2) -> (i32, i32) {
+        let cell = (local / self.cell_size).floor();
+        (cell.x as i32, cell.y as i32)
+    }
+
+    fn insert(&mut self, point: IndexedPoint) {
+        let cell = self.cell(point.local);
+        self.cells.entry(cell).or_default().push(point);
+    }
+
+    /// Nearest point to `local` that is less than `radius` pixels away.
+    pub fn nearest(&self, local: Vec2, radius: f32) -> Option<&IndexedPoint> {
+        let (i, j) = self.cell(local);
+        let reach = (radius / self.cell_size).ceil() as i32;
+
+        (i - reach..=i + reach)
+            .flat_map(|ci| (j - reach..=j + reach).map(move |cj| (ci, cj)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|point| (point, point.local.distance(local)))
+            .filter(|(_, distance)| *distance < radius)
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(point, _)| point)
+    }
+}
+
+/// Spatial index of the visible data points of each plot. It is rebuilt every time a plot is respawned,
+/// that is whenever its axes are moved or zoomed.
+#[derive(Resource, Debug, Clone, Default)]
+pub(crate) struct PointIndex {
+    pub plots: HashMap<PlotId, PointGrid>,
+}
+
+fn index_plot(plot: &Plot) -> PointGrid {
+    let mut grid = PointGrid::new(plot.tooltip_radius);
+    let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+
+    let mut add_series = |series: SeriesId, points: &mut dyn Iterator<Item = Vec2>, y_axis: YAxis, color: Color| {
+        for (index, position) in points.enumerate() {
+            let local = plot.to_local_axis(position, y_axis);
+            if local.x.abs() <= half_size.x && local.y.abs() <= half_size.y {
+                grid.insert(IndexedPoint {
+                    series,
+                    index,
+                    local,
+                    position,
+                    color,
+                });
+            }
+        }
+    };
+
+    // only the series drawn by the renderers are indexed, so that no invisible point can be hovered
+    for (k, segments) in plot.data.segment_groups.iter().enumerate() {
+        if segments.line_style == LineStyle::None {
+            continue;
+        }
+
+        add_series(
+            SeriesId::Segments(k),
+            &mut segments.data.iter().copied(),
+            segments.y_axis,
+            segments.color,
+        );
+    }
+
+    for (k, markers) in plot.data.marker_groups.iter().enumerate() {
+        if markers.marker_style == MarkerStyle::None {
+            continue;
+        }
+
+        add_series(
+            SeriesId::Markers(k),
+            &mut markers.data.iter().copied(),
+            markers.y_axis,
+            markers.color,
+        );
+    }
+
+    for (k, stems) in plot.data.stem_groups.iter().enumerate() {
+        add_series(
+            SeriesId::Stems(k),
+            &mut stems.data.iter().copied(),
+            stems.y_axis,
+            stems.marker.color,
+        );
+    }
+
+    for (k, candles) in plot.data.candlestick_groups.iter().enumerate() {
+        add_series(
+            SeriesId::Candles(k),
+            &mut candles.data.iter().map(|candle| Vec2::new(candle.time, candle.close)),
+            candles.y_axis,
+            candles.up_color,
+        );
+    }
+
+    grid
+}
+
+pub(crate) fn build_point_index(
+    plots: Res<PlotMap>,
+    mut point_index: ResMut<PointIndex>,
+    mut respawn_all_event: EventReader<RespawnAllEvent>,
+) {
+    let plot_ids = respawn_all_event
+        .read()
+        .map(|event| event.plot_id)
+        .collect::<HashSet<PlotId>>();
+
+    for plot_id in plot_ids {
+        match plots.get(&plot_id) {
+            Some(plot) => {
+                point_index.plots.insert(plot_id, index_plot(plot));
+            }
+            None => {
+                point_index.plots.remove(&plot_id);
+            }
+        }
+    }
+}
+
+/// Name of a series, as given with [`Opt::Name`].
+pub(crate) fn series_name(plot: &Plot, series: SeriesId) -> Option<String> {
+    match series {
+        SeriesId::Segments(k) => plot.data.segment_groups.get(k).and_then(|s| s.name.clone()),
+        SeriesId::Markers(k) => plot.data.marker_groups.get(k).and_then(|s| s.name.clone()),
+        SeriesId::Stems(k) => plot.data.stem_groups.get(k).and_then(|s| s.name.clone()),
+        SeriesId::Candles(k) => plot.data.candlestick_groups.get(k).and_then(|s| s.name.clone()),
+    }
+}
+
+fn default_tooltip_text(plot: &Plot, point: &HoveredPoint) -> String {
+    let name = point.series_name.clone().unwrap_or_else(|| match point.series {
+        SeriesId::Segments(k) => format!("line {}", k),
+        SeriesId::Markers(k) => format!("markers {}", k),
+        SeriesId::Stems(k) => format!("stems {}", k),
+        SeriesId::Candles(k) => format!("candles {}", k),
+    });
+
+    let format = |v: f32| format_numeric_label(plot, v, v != 0.0 && !(0.01..1000.0).contains(&v.abs()));
+
+    format!(
+        "{} [{}]\nx: {}\ny: {}",
+        name,
+        point.index,
+        format(point.position.x),
+        format(point.position.y)
+    )
+}
+
+/// Nearest data point to the cursor, if the cursor is over the inner area of the canvas of `plot`.
+pub(crate) fn hovered_point<'a>(plot: &Plot, point_index: &'a PointIndex, cursor: &Cursor) -> Option<&'a IndexedPoint> {
+    let local = cursor.position - plot.canvas_position;
+    let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+
+    if local.x.abs() > half_size.x || local.y.abs() > half_size.y {
+        return None;
+    }
+
+    point_index
+        .plots
+        .get(&plot.id)
+        .and_then(|grid| grid.nearest(local, plot.tooltip_radius))
+}
+
+/// Data point under the cursor for each plot, if any.
+#[derive(Resource, Debug, Clone, Default)]
+pub(crate) struct HoveredPoints {
+    pub points: HashMap<PlotId, IndexedPoint>,
+}
+
+fn same_point(a: &IndexedPoint, b: &IndexedPoint) -> bool {
+    a.series == b.series && a.index == b.index
+}
+
+/// Finds the data point under the cursor for the hovered plot and sends the hover and click events.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_hovered_points(
+    plots: Res<PlotMap>,
+    point_index: Res<PointIndex>,
+    cursor: Res<Cursor>,
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_map: Res<PlotInputMap>,
+    mut hovered_points: ResMut<HoveredPoints>,
+    mut hover_enter_event: EventWriter<PlotPointHoverEnter>,
+    mut hover_leave_event: EventWriter<PlotPointHoverLeave>,
+    mut clicked_event: EventWriter<PlotPointClicked>,
+) {
+    let mut plot_ids = plots.plots.keys().copied().collect::<HashSet<PlotId>>();
+    plot_ids.extend(hovered_points.points.keys().copied());
+
+    for plot_id in plot_ids {
+        // only the canvas receiving the mouse inputs can have a hovered point, so overlapping canvases
+        // do not both send events
+        let hovered = plots
+            .get(&plot_id)
+            .filter(|plot| plot.interaction.points && cursor.hovered_plot == Some(plot_id))
+            .and_then(|plot| hovered_point(plot, &point_index, &cursor))
+            .cloned();
+        let previous = hovered_points.points.get(&plot_id);
+
+        let changed = match (previous, hovered.as_ref()) {
+            (Some(a), Some(b)) => !same_point(a, b),
+            (None, None) => false,
+            _ => true,
+        };
+
+        if changed {
+            if let Some(point) = previous {
+                hover_leave_event.send(PlotPointHoverLeave {
+                    plot_id,
+                    series: point.series,
+                    index: point.index,
+                    position: point.position,
+                });
+            }
+
+            if let Some(point) = hovered.as_ref() {
+                hover_enter_event.send(PlotPointHoverEnter {
+                    plot_id,
+                    series: point.series,
+                    index: point.index,
+                    position: point.position,
+                });
+            }
+        }
+
+        match hovered {
+            Some(point) => {
+                if mouse_button_input.just_pressed(input_map.primary_button) {
+                    clicked_event.send(PlotPointClicked {
+                        plot_id,
+                        series: point.series,
+                        index: point.index,
+                        position: point.position,
+                    });
+                }
+                hovered_points.points.insert(plot_id, point);
+            }
+            None => {
+                hovered_points.points.remove(&plot_id);
+            }
+        }
+    }
+}
+
+/// Highlights the data point under the cursor and shows a tooltip next to it.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn update_tooltips(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut shape_materials: ResMut<Assets<ShapeMaterial>>,
+    maybe_font: Res<TickLabelFont>,
+    plots: Res<PlotMap>,
+    hovered_points: Res<HoveredPoints>,
+    query: Query<(Entity, &PlotIdComponent), With<TooltipPart>>,
+    mut shown: Local<HashMap<PlotId, (SeriesId, usize, Vec2)>>,
+) {
+    for (plot_id, plot) in plots.plots.iter() {
+        let hovered = if plot.show_tooltips {
+            hovered_points.points.get(plot_id)
+        } else {
+            None
+        };
+
+        // the tooltip is respawned when the point changes or moves on the screen
+        let key = hovered.map(|point| (point.series, point.index, point.local + plot.canvas_position));
+        if shown.get(plot_id).copied() == key {
+            continue;
+        }
+
+        for (entity, plot_id_comp) in query.iter() {
+            if plot_id_comp.0 == *plot_id {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        match key {
+            Some(key) => shown.insert(*plot_id, key),
+            None => shown.remove(plot_id),
+        };
+
+        let Some(point) = hovered else {
+            continue;
+        };
+
+        let mut builder = ShapeMeshBuilder::default();
+        builder.disk(point.local, 7.0, Color::srgba(0.0, 0.0, 0.0, 0.7));
+        builder.disk(point.local, 5.0, point.color);
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            builder,
+            1.25,
+            TooltipPart,
+        );
+
+        let Some(font) = maybe_font.maybe_font.as_ref() else {
+            continue;
+        };
+
+        let hovered_point = HoveredPoint {
+            plot_id: *plot_id,
+            series: point.series,
+            series_name: series_name(plot, point.series),
+            index: point.index,
+            position: point.position,
+        };
+
+        let text = match plot.tooltip_formatter.as_ref() {
+            Some(formatter) => formatter.format(&hovered_point),
+            None => default_tooltip_text(plot, &hovered_point),
+        };
+
+        // the size of the text is estimated, since it is only known once the text has been laid out
+        let font_size = 14.0;
+        let padding = 4.0;
+        let num_chars = text.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+        let num_lines = text.lines().count().max(1);
+        let size = Vec2::new(
+            num_chars as f32 * font_size * 0.55 + padding * 2.0,
+            num_lines as f32 * font_size * 1.25 + padding * 2.0,
+        );
+
+        // the tooltip goes to the top right of the point, unless it would leave the canvas
+        let half_size = plot.canvas_size / (1.0 + plot.outer_border) / 2.0;
+        let offset = 10.0;
+        let mut top_left = point.local + Vec2::new(offset, offset + size.y);
+        if top_left.x + size.x > half_size.x {
+            top_left.x = point.local.x - offset - size.x;
+        }
+        if top_left.y > half_size.y {
+            top_left.y = point.local.y - offset;
+        }
+
+        let mut builder = ShapeMeshBuilder::default();
+        let lo = top_left - Vec2::new(0.0, size.y);
+        let up = top_left + Vec2::new(size.x, 0.0);
+        builder.rect(lo - 1.0, up + 1.0, Color::srgba(0.0, 0.0, 0.0, 0.6));
+        builder.rect(lo, up, Color::srgba(1.0, 1.0, 1.0, 0.9));
+        spawn_shape_mesh(
+            &mut commands,
+            &mut meshes,
+            &mut shape_materials,
+            plot,
+            builder,
+            1.3,
+            TooltipPart,
+        );
+
+        spawn_shape_label(
+            &mut commands,
+            plot,
+            &text,
+            top_left + Vec2::new(padding, -padding),
+            1.31,
+            font_size,
+            plot.tick_label_color,
+            font,
+            bevy::sprite::Anchor::TopLeft,
+            TooltipPart,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(cell_size: f32, locals: &[Vec2]) -> PointGrid {
+        let mut grid = PointGrid::new(cell_size);
+        for (index, local) in locals.iter().enumerate() {
+            grid.insert(IndexedPoint {
+                series: SeriesId::Segments(0),
+                index,
+                local: *local,
+                position: *local,
+                color: Color::WHITE,
+            });
+        }
+        grid
+    }
+
+    #[test]
+    fn nearest_picks_the_closest_point() {
+        let grid = grid(10.0, &[Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(-3.0, 0.0)]);
+
+        let nearest = grid.nearest(Vec2::new(2.5, 0.0), 10.0).map(|point| point.index);
+        assert_eq!(nearest, Some(1));
+    }
+
+    #[test]
+    fn nearest_ignores_the_points_outside_the_radius() {
+        let grid = grid(10.0, &[Vec2::new(0.0, 0.0)]);
+
+        assert!(grid.nearest(Vec2::new(10.0, 0.0), 10.0).is_none());
+        assert!(grid.nearest(Vec2::new(6.0, 8.0), 10.0).is_none());
+        assert!(grid.nearest(Vec2::new(6.0, 7.9), 10.0).is_some());
+    }
+
+    #[test]
+    fn nearest_looks_into_the_neighbouring_cells() {
+        // the two points are on both sides of cell boundaries, including the negative ones
+        let grid = grid(5.0, &[Vec2::new(-0.5, -0.5), Vec2::new(11.0, 0.0)]);
+
+        let nearest = grid.nearest(Vec2::new(0.5, 0.5), 3.0).map(|point| point.index);
+        assert_eq!(nearest, Some(0));
+
+        // a radius larger than the cells reaches several cells away
+        let nearest = grid.nearest(Vec2::new(3.0, 0.0), 9.0).map(|point| point.index);
+        assert_eq!(nearest, Some(0));
+        let nearest = grid.nearest(Vec2::new(7.0, 0.0), 9.0).map(|point| point.index);
+        assert_eq!(nearest, Some(1));
+    }
+}
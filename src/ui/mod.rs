@@ -0,0 +1,117 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+
+use crate::inputs::*;
+use crate::plot::*;
+
+/// Shows a plot inside a `bevy_ui` layout, for instance in a menu or a side panel. The node is sized and
+/// positioned by the flexbox layout like any other node, and the canvas of the plot follows its size:
+///
+/// ```ignore
+/// commands.spawn((
+///     PlotNode::new(plot_id, 1),
+///     Node { width: Val::Percent(50.0), height: Val::Px(300.0), ..default() },
+/// ));
+/// ```
+///
+/// The plot is rendered offscreen (see [`Plot::render_to_texture`]) and shown as the image of the node.
+/// The plot reacts to the mouse only while the node is hovered or pressed, as reported by its
+/// [`Interaction`], with the interactions and tooltips set on the plot itself ([`Plot::interaction`] and
+/// [`Plot::show_tooltips`]). The offscreen canvas cannot be reached by the cursor of the window, but it
+/// still lives in the world on its own render layer: keep its `canvas_position` away from the canvases of
+/// the plots shown in the window.
+#[derive(Component, Debug, Clone)]
+#[require(Node, ImageNode, Interaction, RelativeCursorPosition)]
+pub struct PlotNode {
+    pub plot_id: PlotId,
+
+    /// Render layer of the plot, which must differ from the layer of any other plot rendered to a texture
+    pub layer: usize,
+}
+
+impl PlotNode {
+    pub fn new(plot_id: PlotId, layer: usize) -> Self {
+        PlotNode { plot_id, layer }
+    }
+}
+
+/// Renders the plot of each [`PlotNode`] to the image of the node, with a canvas the size of the node.
+pub(crate) fn layout_plot_nodes(
+    mut commands: Commands,
+    mut plots: ResMut<PlotMap>,
+    mut images: ResMut<Assets<Image>>,
+    mut nodes: Query<(&PlotNode, &ComputedNode, &mut ImageNode)>,
+) {
+    for (plot_node, computed_node, mut image_node) in nodes.iter_mut() {
+        let Some(plot) = plots.get_mut(&plot_node.plot_id) else {
+            continue;
+        };
+
+        let has_texture = plot
+            .render_target
+            .as_ref()
+            .is_some_and(|texture| texture.layer == plot_node.layer);
+
+        // unlike a plain texture, the node hands the cursor to the plot, which keeps its interactions
+        if !has_texture {
+            let (interaction, show_tooltips) = (plot.interaction, plot.show_tooltips);
+            plot.render_to_texture(&mut images, plot_node.layer);
+            plot.interaction = interaction;
+            plot.show_tooltips = show_tooltips;
+        }
+
+        if let Some(texture) = plot.render_target.as_ref() {
+            if image_node.image != texture.image {
+                image_node.image = texture.image.clone();
+            }
+        }
+
+        // the computed size is in physical pixels, the canvas size in logical pixels
+        let size = computed_node.size() * computed_node.inverse_scale_factor();
+        if size.cmple(Vec2::ZERO).any() || plot.canvas_size.distance(size) < 0.5 {
+            continue;
+        }
+
+        plot.canvas_size = size;
+
+        if let Some(entity) = plot.entity.take() {
+            commands.entity(entity).despawn_recursive();
+            plot.do_spawn_plot = true;
+        }
+    }
+}
+
+/// Hands the cursor to the plot of the [`PlotNode`] under it: the cursor is moved to the matching point of the
+/// offscreen canvas, which becomes the only canvas the cursor can hover.
+pub(crate) fn plot_node_cursor(
+    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    input_map: Res<PlotInputMap>,
+    mut cursor_res: ResMut<Cursor>,
+    plots: Res<PlotMap>,
+    nodes: Query<(&PlotNode, &Interaction, &RelativeCursorPosition)>,
+) {
+    for (plot_node, interaction, relative_cursor) in nodes.iter() {
+        let Some(plot) = plots.get(&plot_node.plot_id) else {
+            continue;
+        };
+
+        // a pressed node keeps the cursor while dragging outside of it
+        let Some(normalized) = relative_cursor
+            .normalized
+            .filter(|_| *interaction != Interaction::None)
+        else {
+            continue;
+        };
+
+        cursor_res.node_plot = Some(plot.id);
+
+        // (0, 0) is the top left corner of the node and (1, 1) its bottom right corner
+        let offset = (normalized - 0.5) * plot.canvas_size * Vec2::new(1.0, -1.0);
+        cursor_res.position = plot.canvas_position + offset;
+
+        if mouse_button_input.just_pressed(input_map.primary_button) {
+            cursor_res.last_click_position = cursor_res.position;
+        }
+
+        cursor_res.pos_relative_to_click = cursor_res.position - cursor_res.last_click_position;
+    }
+}